@@ -0,0 +1,209 @@
+use std::mem;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::{Engine, Neighborhood};
+use crate::rule::LifeRule;
+use crate::zone::Zone;
+use crate::{Boundary, Universe};
+
+// Side of the square tiles the grid is split into for skipping quiet regions
+pub(crate) const TILE: u32 = 32;
+
+// Cells a tick has to recompute, the rest staying as they are
+pub(crate) enum Active {
+    // Grid indices in row-major order
+    Cells(Vec<usize>),
+    // One flag per `TILE` x `TILE` tile, row by row, for tiles near a change
+    Tiles(Vec<bool>),
+}
+
+// Everything a birth/survival tick depends on, as it stood after the last tick,
+// so the next one can tell whether only cells near that tick's changes can change
+#[derive(Clone, Debug)]
+pub(crate) struct Activity {
+    // Cleared by anything that changes cells or flags outside a tick
+    valid: bool,
+    width: u32,
+    height: u32,
+    rule: LifeRule,
+    neighborhood: Neighborhood,
+    boundary: Boundary,
+    count_self: bool,
+    zones: Vec<Zone>,
+    // Flat [row, col, ...] list of the cells the tick changed
+    pub changed: Vec<u32>,
+    // One flag per tile, row by row, for tiles holding a cell the tick changed
+    tiles: Vec<bool>,
+    // The same for the tick in progress, set as cells change
+    pending: Vec<bool>,
+}
+
+impl Activity {
+    pub fn new() -> Activity {
+        Activity {
+            valid: false,
+            width: 0,
+            height: 0,
+            rule: LifeRule::CONWAY,
+            neighborhood: Neighborhood::Moore,
+            boundary: Boundary::Wrap,
+            count_self: false,
+            zones: Vec::new(),
+            changed: Vec::new(),
+            tiles: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    // Makes the next tick recompute every cell
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    // Starts collecting the changed tiles of a tick over `tiles` tiles
+    pub fn begin_tick(&mut self, tiles: usize) {
+        self.pending.clear();
+        self.pending.resize(tiles, false);
+    }
+
+    pub fn mark(&mut self, tile: usize) {
+        self.pending[tile] = true;
+    }
+}
+
+impl Universe {
+    // Remembers the outcome of a deterministic birth/survival tick; random births,
+    // survivals and noise can happen anywhere, so those always recompute every cell
+    pub(crate) fn save_activity(&mut self) {
+        let deterministic =
+            self.birth_chance >= 1.0 && self.survival_chance >= 1.0 && self.noise <= 0.0;
+        let rule = match self.engine {
+            Engine::Life(rule) if deterministic => rule,
+            _ => {
+                self.activity.valid = false;
+                return;
+            }
+        };
+        let activity = &mut self.activity;
+        activity.valid = true;
+        activity.width = self.width;
+        activity.height = self.height;
+        activity.rule = rule;
+        activity.neighborhood = self.neighborhood;
+        activity.boundary = self.boundary;
+        activity.count_self = self.count_self;
+        activity.zones.clone_from(&self.zones);
+        activity.changed.clone_from(&self.changed);
+        mem::swap(&mut activity.tiles, &mut activity.pending);
+    }
+
+    // Where cells may change under `rule` this tick: next to cells that changed
+    // last tick. `None` when every cell needs recomputing, because the grid, its
+    // size, its settings or the rule were touched since, births and survivals
+    // have become random, or too much of the grid is active to be worth skipping.
+    //
+    // The spare buffer then holds the previous generation, which differs from the
+    // current one exactly at the cells in the activity's `changed`.
+    pub(crate) fn active_region(&self, rule: LifeRule) -> Option<Active> {
+        let activity = &self.activity;
+        let untouched = activity.valid
+            && activity.width == self.width
+            && activity.height == self.height
+            && self.birth_chance >= 1.0
+            && self.survival_chance >= 1.0
+            && activity.rule == rule
+            && activity.neighborhood == self.neighborhood
+            && activity.boundary == self.boundary
+            && activity.count_self == self.count_self
+            && activity.zones == self.zones
+            && self.back.len() == self.cells.len();
+        // Hexagonal neighbors only pair up both ways when wrapping rows line up
+        let symmetric = self.neighborhood != Neighborhood::Hexagonal
+            || self.boundary == Boundary::Fixed
+            || self.height.is_multiple_of(2);
+        if !untouched || !symmetric {
+            return None;
+        }
+
+        // A handful of changes are cheapest to follow one cell at a time
+        if activity.changed.len() / 2 * 9 <= (TILE * TILE) as usize {
+            let mut active = Vec::new();
+            for pair in activity.changed.chunks_exact(2) {
+                let (row, col) = (pair[0], pair[1]);
+                active.push(self.get_index(row, col));
+                let offsets = self.neighborhood.offsets(row);
+                active.extend(
+                    offsets
+                        .iter()
+                        .filter_map(|&offset| self.neighbor_index(row, col, offset)),
+                );
+            }
+            active.sort_unstable();
+            active.dedup();
+            return Some(Active::Cells(active));
+        }
+
+        // Otherwise every changed tile and the tiles around it, wrapping; once most
+        // tiles are in, skipping the rest saves less than it costs
+        let (across, down) = self.tile_counts();
+        let mut tiles = vec![false; activity.tiles.len()];
+        let mut count = 0;
+        for (tile, _) in activity.tiles.iter().enumerate().filter(|(_, &on)| on) {
+            let (tile_row, tile_col) = (tile as u32 / across, tile as u32 % across);
+            for delta_row in [down - 1, 0, 1] {
+                for delta_col in [across - 1, 0, 1] {
+                    let row = (tile_row + delta_row) % down;
+                    let col = (tile_col + delta_col) % across;
+                    let neighbor = &mut tiles[(row * across + col) as usize];
+                    count += !*neighbor as usize;
+                    *neighbor = true;
+                }
+            }
+        }
+        if count * 2 > tiles.len() {
+            return None;
+        }
+        Some(Active::Tiles(tiles))
+    }
+
+    // The tile holding a cell, counting row by row
+    pub(crate) fn tile_index(&self, row: u32, col: u32) -> usize {
+        (row / TILE * self.width.div_ceil(TILE) + col / TILE) as usize
+    }
+
+    // Tiles across and down the grid, counting partial tiles at the edges
+    pub(crate) fn tile_counts(&self) -> (u32, u32) {
+        (self.width.div_ceil(TILE), self.height.div_ceil(TILE))
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Side of the square tiles `dirty_tiles()` reports, in cells.
+    pub fn tile_size(&self) -> u32 {
+        TILE
+    }
+
+    /// Flat `[row, col, height, width, ...]` list of the tiles holding cells flipped
+    /// by the most recent `tick()`, in row-major order and clipped to the grid, so
+    /// a renderer can redraw just those. Tiles are `tile_size()` cells square.
+    pub fn dirty_tiles(&self) -> Vec<u32> {
+        let (across, _) = self.tile_counts();
+        let mut tiles: Vec<u32> = self
+            .changed
+            .chunks_exact(2)
+            .map(|pair| pair[0] / TILE * across + pair[1] / TILE)
+            .collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+
+        let mut rects = Vec::with_capacity(tiles.len() * 4);
+        for tile in tiles {
+            let (row, col) = (tile / across * TILE, tile % across * TILE);
+            let height = TILE.min(self.height - row);
+            let width = TILE.min(self.width - col);
+            rects.extend_from_slice(&[row, col, height, width]);
+        }
+        rects
+    }
+}
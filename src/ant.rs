@@ -0,0 +1,250 @@
+use std::fmt;
+use std::mem;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::rule::LifeRule;
+use crate::{Boundary, Cell, Direction, Error, Universe};
+
+/// An agent walking the grid under an ant rule such as Langton's `"RL"`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ant {
+    pub row: u32,
+    pub col: u32,
+    pub direction: Direction,
+}
+
+// What an ant does on a cell of a given color before moving on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Turn {
+    Left,
+    Right,
+    // Keep going straight
+    None,
+    // Turn around
+    U,
+}
+
+// The colors' turns and the ants following them
+#[derive(Clone, Debug)]
+pub(crate) struct Langton {
+    turns: Vec<Turn>,
+    ants: Vec<Ant>,
+}
+
+impl Langton {
+    pub fn state_count(&self) -> u32 {
+        self.turns.len() as u32
+    }
+
+    // Carries the ants through a change of grid shape, given the new index of each
+    // old cell in an old grid `width` cells wide. Ants turn with the grid and are
+    // dropped if their cell was cut off.
+    pub fn remap(&mut self, width: u32, new_width: u32, targets: &[Option<usize>]) {
+        let height = (targets.len() / width as usize) as i64;
+        let position = |idx: usize| {
+            (
+                (idx / new_width as usize) as i64,
+                (idx % new_width as usize) as i64,
+            )
+        };
+        self.ants.retain_mut(|ant| {
+            let Some(target) = targets[(ant.row * width + ant.col) as usize] else {
+                return false;
+            };
+            let (row, col) = position(target);
+
+            // Face wherever the cell ahead ended up, or away from the cell behind,
+            // as long as it is still next door
+            let (dr, dc) = step(ant.direction);
+            for sign in [1, -1] {
+                let (near_row, near_col) = (ant.row as i64 + sign * dr, ant.col as i64 + sign * dc);
+                if !(0..height).contains(&near_row) || !(0..width as i64).contains(&near_col) {
+                    continue;
+                }
+                let near = targets[(near_row * width as i64 + near_col) as usize].map(position);
+                let facing = near.and_then(|(near_row, near_col)| {
+                    let offset = (sign * (near_row - row), sign * (near_col - col));
+                    DIRECTIONS
+                        .iter()
+                        .find(|&&direction| step(direction) == offset)
+                });
+                if let Some(&direction) = facing {
+                    ant.direction = direction;
+                    break;
+                }
+            }
+            ant.row = row as u32;
+            ant.col = col as u32;
+            true
+        });
+    }
+}
+
+impl fmt::Display for Langton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for turn in self.turns.iter() {
+            let letter = match turn {
+                Turn::Left => 'L',
+                Turn::Right => 'R',
+                Turn::None => 'N',
+                Turn::U => 'U',
+            };
+            write!(f, "{}", letter)?;
+        }
+        Ok(())
+    }
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+// Row and column offsets of one step forward
+fn step(direction: Direction) -> (i64, i64) {
+    match direction {
+        Direction::Up => (-1, 0),
+        Direction::Down => (1, 0),
+        Direction::Left => (0, -1),
+        Direction::Right => (0, 1),
+    }
+}
+
+fn turn(direction: Direction, turn: Turn) -> Direction {
+    // Clockwise order
+    const ORDER: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+    let position = ORDER.iter().position(|&d| d == direction).unwrap();
+    let steps = match turn {
+        Turn::None => 0,
+        Turn::Right => 1,
+        Turn::U => 2,
+        Turn::Left => 3,
+    };
+    ORDER[(position + steps) % 4]
+}
+
+impl Universe {
+    // Moves every ant one step: turn by the color underneath, advance that cell's
+    // color, then step forward. Ants that walk off a fixed edge are removed.
+    pub(crate) fn tick_ants(&mut self) {
+        self.sync_states();
+        let mut engine = mem::replace(&mut self.engine, Engine::Life(LifeRule::CONWAY));
+        let Engine::Langton(langton) = &mut engine else {
+            unreachable!("only ant rules move ants");
+        };
+        let Langton { turns, ants } = &mut **langton;
+        let colors = turns.len() as u8;
+        self.changed.clear();
+
+        let (height, width) = (self.height as i64, self.width as i64);
+        // Ants left outside by a resize that did not carry them along
+        ants.retain(|ant| ant.row < self.height && ant.col < self.width);
+        ants.retain_mut(|ant| {
+            let idx = self.get_index(ant.row, ant.col);
+            let color = self.states[idx];
+            ant.direction = turn(ant.direction, turns[color as usize]);
+            self.states[idx] = (color + 1) % colors;
+            self.cells[idx] = if self.states[idx] == 0 {
+                Cell::Dead
+            } else {
+                Cell::Alive
+            };
+            self.changed.push(ant.row);
+            self.changed.push(ant.col);
+
+            let (dr, dc) = step(ant.direction);
+            let (row, col) = (ant.row as i64 + dr, ant.col as i64 + dc);
+            let (row, col) = match self.boundary {
+                Boundary::Wrap => (row.rem_euclid(height), col.rem_euclid(width)),
+                Boundary::Fixed if row < 0 || col < 0 || row >= height || col >= width => {
+                    return false
+                }
+                Boundary::Fixed => (row, col),
+            };
+            ant.row = row as u32;
+            ant.col = col as u32;
+            true
+        });
+
+        self.engine = engine;
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to ant mode: `rule` gives, for each cell color in turn, whether an ant
+    /// standing on it turns `L`eft, `R`ight, goes straight (`N`) or turns around
+    /// (`U`). Langton's ant is `"RL"`; longer strings such as `"LLRR"` use more colors,
+    /// read with `state()`. Existing ants are kept.
+    pub fn set_ant_rule(&mut self, rule: &str) -> Result<(), Error> {
+        let turns = rule
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'L' => Ok(Turn::Left),
+                'R' => Ok(Turn::Right),
+                'N' => Ok(Turn::None),
+                'U' => Ok(Turn::U),
+                _ => Err(Error::InvalidRule(rule.to_string())),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if turns.len() < 2 || turns.len() > 255 {
+            return Err(Error::InvalidRule(rule.to_string()));
+        }
+
+        // Keep live cells on the first nonzero color
+        self.sync_states();
+        for state in self.states.iter_mut() {
+            *state = (*state).min(1);
+        }
+        let ants = match mem::replace(&mut self.engine, Engine::Life(LifeRule::CONWAY)) {
+            Engine::Langton(langton) => langton.ants,
+            _ => Vec::new(),
+        };
+        self.engine = Engine::Langton(Box::new(Langton { turns, ants }));
+        Ok(())
+    }
+
+    /// Places an ant facing `direction`, switching to Langton's `"RL"` rule if no
+    /// ant rule is active.
+    pub fn add_ant(&mut self, row: u32, col: u32, direction: Direction) -> Result<(), Error> {
+        self.checked_index(row, col)?;
+        if !matches!(self.engine, Engine::Langton(_)) {
+            self.set_ant_rule("RL")?;
+        }
+        if let Engine::Langton(langton) = &mut self.engine {
+            langton.ants.push(Ant {
+                row,
+                col,
+                direction,
+            });
+        }
+        Ok(())
+    }
+
+    /// Flat `[row, col, direction, ...]` list of the ants, `direction` as in `Direction`.
+    pub fn ants(&self) -> Vec<u32> {
+        match &self.engine {
+            Engine::Langton(langton) => langton
+                .ants
+                .iter()
+                .flat_map(|ant| [ant.row, ant.col, ant.direction as u32])
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn clear_ants(&mut self) {
+        if let Engine::Langton(langton) = &mut self.engine {
+            langton.ants.clear();
+        }
+    }
+}
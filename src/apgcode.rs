@@ -0,0 +1,210 @@
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::{Orientation, Pattern};
+use crate::{Error, SparseUniverse, Universe};
+
+// How long `apgcode()` looks for a repeat before giving up
+const MAX_PERIOD: u32 = 1024;
+
+// Wechsler digits for one 5-cell column, top row in the lowest bit
+const DIGITS: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+// Digits after `y`, counting runs of 4 to 39 empty columns
+const RUN_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Extended Wechsler notation for a pattern exactly as it is oriented: strips of
+/// five rows separated by `z`, one digit per column, with `w`, `x` and `y?`
+/// standing for runs of empty columns.
+pub fn wechsler(pattern: &Pattern) -> String {
+    let strips = pattern.height.div_ceil(5);
+    let mut columns = vec![vec![0u8; pattern.width as usize]; strips as usize];
+    for &(row, col) in pattern.cells.iter() {
+        columns[(row / 5) as usize][col as usize] |= 1 << (row % 5);
+    }
+
+    let mut code = String::new();
+    for (i, strip) in columns.iter().enumerate() {
+        if i > 0 {
+            code.push('z');
+        }
+        let used = strip
+            .iter()
+            .rposition(|&column| column != 0)
+            .map_or(0, |i| i + 1);
+        let mut zeros = 0;
+        for &column in &strip[..used] {
+            if column == 0 {
+                zeros += 1;
+                continue;
+            }
+            push_zeros(&mut code, zeros);
+            zeros = 0;
+            code.push(DIGITS[column as usize] as char);
+        }
+    }
+    code
+}
+
+fn push_zeros(code: &mut String, mut zeros: usize) {
+    while zeros > 0 {
+        match zeros {
+            1 => code.push('0'),
+            2 => code.push('w'),
+            3 => code.push('x'),
+            _ => {
+                let run = (zeros - 4).min(RUN_DIGITS.len() - 1);
+                code.push('y');
+                code.push(RUN_DIGITS[run] as char);
+                zeros -= run + 4;
+                continue;
+            }
+        }
+        return;
+    }
+}
+
+/// Decodes extended Wechsler notation, with or without an `xs`/`xp`/`xq` prefix.
+pub fn parse(code: &str) -> Result<Pattern, Error> {
+    let body = match code.find('_') {
+        Some(i) if code.starts_with('x') => &code[i + 1..],
+        _ => code,
+    };
+
+    let mut cells = Vec::new();
+    let (mut top, mut col) = (0u32, 0u32);
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            'z' => {
+                top += 5;
+                col = 0;
+            }
+            'w' => col += 2,
+            'x' => col += 3,
+            'y' => {
+                let run = chars
+                    .next()
+                    .and_then(|c| RUN_DIGITS.iter().position(|&d| d as char == c))
+                    .ok_or_else(|| parse_error("'y' must be followed by 0-9 or a-z"))?;
+                col += 4 + run as u32;
+            }
+            c => {
+                let bits = DIGITS
+                    .iter()
+                    .position(|&d| d as char == c)
+                    .ok_or_else(|| parse_error(&format!("unexpected character '{}'", c)))?;
+                for bit in 0..5 {
+                    if bits & (1 << bit) != 0 {
+                        cells.push((top + bit, col));
+                    }
+                }
+                col += 1;
+            }
+        }
+    }
+
+    let points: Vec<_> = cells
+        .iter()
+        .map(|&(row, col)| (row as i32, col as i32))
+        .collect();
+    Pattern::from_points(&points)
+}
+
+fn parse_error(message: &str) -> Error {
+    Error::Parse {
+        line: 1,
+        message: message.to_string(),
+    }
+}
+
+// The shortest, then alphabetically first, code over all phases and orientations
+fn canonical(phases: &[Pattern]) -> String {
+    phases
+        .iter()
+        .flat_map(|phase| {
+            Orientation::ALL
+                .iter()
+                .map(move |&o| wechsler(&phase.oriented(o)))
+        })
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+        .unwrap_or_default()
+}
+
+// One period of a repeating object run under Conway's Life
+pub(crate) struct Cycle {
+    pub phases: Vec<Pattern>,
+    pub period: u32,
+    /// How far the object moves each period.
+    pub shift: (i32, i32),
+}
+
+// Runs the live cells of `universe` until they repeat, up to `MAX_PERIOD` generations
+pub(crate) fn cycle(mut universe: SparseUniverse) -> Result<Cycle, Error> {
+    let start = live_points(&universe);
+    if start.is_empty() {
+        return Err(Error::NoLiveCells);
+    }
+
+    let mut phases = vec![Pattern::from_points(&start)?];
+    for period in 1..=MAX_PERIOD {
+        universe.tick();
+        let points = live_points(&universe);
+        if points.is_empty() {
+            break;
+        }
+
+        if points.len() == start.len() {
+            let (dr, dc) = (points[0].0 - start[0].0, points[0].1 - start[0].1);
+            let repeats = points
+                .iter()
+                .zip(start.iter())
+                .all(|(p, s)| p.0 - s.0 == dr && p.1 - s.1 == dc);
+            if repeats {
+                return Ok(Cycle {
+                    phases,
+                    period,
+                    shift: (dr, dc),
+                });
+            }
+        }
+        phases.push(Pattern::from_points(&points)?);
+    }
+
+    Err(Error::Aperiodic {
+        generations: MAX_PERIOD,
+    })
+}
+
+fn live_points(universe: &SparseUniverse) -> Vec<(i32, i32)> {
+    universe
+        .live_cells()
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// The apgcode of the live cells, taken as a single object run on an unbounded
+    /// plane: `xs<population>_` for still lifes, `xp<period>_` for oscillators and
+    /// `xq<period>_` for spaceships, followed by the canonical Wechsler code.
+    pub fn apgcode(&self) -> Result<String, Error> {
+        let mut universe = SparseUniverse::new();
+        for (row, col) in self.iter_live_cells() {
+            universe.set_alive(row as i32, col as i32);
+        }
+        let cycle = cycle(universe)?;
+        let population = cycle.phases[0].cells.len();
+        let code = canonical(&cycle.phases);
+        Ok(match (cycle.period, cycle.shift) {
+            (1, (0, 0)) => format!("xs{}_{}", population, code),
+            (_, (0, 0)) => format!("xp{}_{}", cycle.period, code),
+            _ => format!("xq{}_{}", cycle.period, code),
+        })
+    }
+
+    /// Creates a `width` x `height` universe with the object named by an apgcode
+    /// (or a bare Wechsler code) centered in it.
+    pub fn from_apgcode(code: &str, width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::from_pattern(&parse(code)?, width, height)
+    }
+}
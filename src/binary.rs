@@ -0,0 +1,168 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{check_size, Boundary, Cell, Error, Neighborhood, Universe};
+
+const MAGIC: &[u8; 3] = b"GOL";
+// Version 2 added the rule settings and the state layer
+const VERSION: u8 = 2;
+// Magic, version, width, height, boundary and layer bits, as in version 1
+const V1_HEADER_LEN: usize = 3 + 1 + 4 + 4 + 1 + 1;
+// Then the neighborhood, whether rules count the cell itself and the length of
+// the rulestring that follows the header
+const HEADER_LEN: usize = V1_HEADER_LEN + 1 + 1 + 2;
+
+// Bits of the header byte saying which optional layers follow the cells
+const HAS_FLAGS: u8 = 1;
+const HAS_TAGS: u8 = 2;
+const HAS_STATES: u8 = 4;
+
+// Packs cells eight to a byte, first cell in the lowest bit
+pub(crate) fn pack_cells(cells: &[Cell]) -> Vec<u8> {
+    cells
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (bit, &cell)| byte | ((cell as u8) << bit))
+        })
+        .collect()
+}
+
+// Unpacks `count` cells written by `pack_cells()`
+pub(crate) fn unpack_cells(bytes: &[u8], count: usize) -> Vec<Cell> {
+    (0..count)
+        .map(|i| match (bytes[i / 8] >> (i % 8)) & 1 {
+            0 => Cell::Dead,
+            _ => Cell::Alive,
+        })
+        .collect()
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidData(message.to_string())
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// A compact versioned snapshot: an 18-byte header and the rulestring, followed
+    /// by the cells packed eight per byte, plus the wall/anchor, tag and state
+    /// layers when they are in use. Undo history and the generator state are not
+    /// included. The rule is saved as `rule()` writes it, so rule tables, kernels,
+    /// Langton's ants and callbacks, which it only names, can't be loaded back.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let has_flags = self.flags.iter().any(|&flags| flags != 0);
+        let mut layers = 0;
+        if has_flags {
+            layers |= HAS_FLAGS;
+        }
+        if !self.tags.is_empty() {
+            layers |= HAS_TAGS;
+        }
+        if !self.states.is_empty() {
+            layers |= HAS_STATES;
+        }
+        let rule = self.rule();
+        let rule = &rule.as_bytes()[..rule.len().min(u16::MAX as usize)];
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + rule.len() + self.cells.len().div_ceil(8));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.push(self.boundary as u8);
+        bytes.push(layers);
+        bytes.push(self.neighborhood as u8);
+        bytes.push(self.count_self as u8);
+        bytes.extend_from_slice(&(rule.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(rule);
+        bytes.extend(pack_cells(&self.cells));
+        if has_flags {
+            bytes.extend_from_slice(&self.flags);
+        }
+        bytes.extend_from_slice(&self.tags);
+        bytes.extend_from_slice(&self.states);
+        bytes
+    }
+
+    /// Restores a universe saved with `to_bytes()`, in this version or the first.
+    /// Fails with `InvalidRule` if the saved rule is one `set_rule()` can't read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Universe, Error> {
+        if bytes.len() < V1_HEADER_LEN || &bytes[..3] != MAGIC {
+            return Err(invalid("missing header"));
+        }
+        let version = bytes[3];
+        if version != 1 && version != VERSION {
+            return Err(invalid(&format!("unsupported version {}", version)));
+        }
+
+        let width = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let height = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        check_size(width, height)?;
+        let boundary = match bytes[12] {
+            0 => Boundary::Wrap,
+            1 => Boundary::Fixed,
+            other => return Err(invalid(&format!("unknown boundary {}", other))),
+        };
+        let layers = bytes[13];
+
+        // Version 1 files hold Conway's Life in the Moore neighborhood
+        let (mut header_len, mut rule, mut neighborhood, mut count_self) =
+            (V1_HEADER_LEN, "B3/S23", Neighborhood::Moore, false);
+        if version != 1 {
+            if bytes.len() < HEADER_LEN {
+                return Err(invalid("missing header"));
+            }
+            neighborhood = match bytes[14] {
+                0 => Neighborhood::Moore,
+                1 => Neighborhood::VonNeumann,
+                2 => Neighborhood::Hexagonal,
+                other => return Err(invalid(&format!("unknown neighborhood {}", other))),
+            };
+            count_self = bytes[15] != 0;
+            let rule_len = u16::from_le_bytes([bytes[16], bytes[17]]) as usize;
+            let rule_bytes = bytes
+                .get(HEADER_LEN..HEADER_LEN + rule_len)
+                .ok_or_else(|| invalid("missing rule"))?;
+            rule = std::str::from_utf8(rule_bytes).map_err(|_| invalid("rule is not UTF-8"))?;
+            header_len = HEADER_LEN + rule_len;
+        }
+
+        let count = (width * height) as usize;
+        let packed = count.div_ceil(8);
+        let mut expected = header_len + packed;
+        for layer in [HAS_FLAGS, HAS_TAGS, HAS_STATES] {
+            if layers & layer != 0 {
+                expected += count;
+            }
+        }
+        if bytes.len() != expected {
+            return Err(invalid(&format!(
+                "expected {} bytes but got {}",
+                expected,
+                bytes.len()
+            )));
+        }
+
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        let mut rest = &bytes[header_len..];
+        universe.cells = unpack_cells(&rest[..packed], count);
+        rest = &rest[packed..];
+        if layers & HAS_FLAGS != 0 {
+            universe.flags = rest[..count].to_vec();
+            rest = &rest[count..];
+        }
+        if layers & HAS_TAGS != 0 {
+            universe.tags = rest[..count].to_vec();
+            rest = &rest[count..];
+        }
+        universe.boundary = boundary;
+        universe.restore_rule(rule, neighborhood, count_self)?;
+        if layers & HAS_STATES != 0 {
+            universe.states = rest.to_vec();
+        }
+        universe.save_initial();
+        Ok(universe)
+    }
+}
@@ -0,0 +1,100 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::{self, Generator};
+use crate::{patterns, Boundary, Error, RngKind, Universe};
+
+/// Chained configuration for creating a `Universe`, e.g. from JS:
+///
+/// ```js
+/// const universe = UniverseBuilder.new()
+///     .size(128, 96)
+///     .seed(42n)
+///     .density(0.2)
+///     .boundary(Boundary.Fixed)
+///     .pattern("glider", 1, 1)
+///     .build();
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniverseBuilder {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,
+    rng: RngKind,
+    density: f64,
+    boundary: Boundary,
+    patterns: Vec<(String, u32, u32)>,
+}
+
+#[wasm_bindgen]
+impl UniverseBuilder {
+    pub fn new() -> UniverseBuilder {
+        UniverseBuilder {
+            width: 64,
+            height: 64,
+            seed: None,
+            rng: RngKind::XorShift,
+            density: 0.5,
+            boundary: Boundary::Wrap,
+            patterns: Vec::new(),
+        }
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> UniverseBuilder {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Makes the initial soup reproducible; without a seed every build differs.
+    pub fn seed(mut self, seed: u64) -> UniverseBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn rng(mut self, kind: RngKind) -> UniverseBuilder {
+        self.rng = kind;
+        self
+    }
+
+    /// Probability of each cell starting alive. Use `0` for an empty universe.
+    pub fn density(mut self, density: f64) -> UniverseBuilder {
+        self.density = density;
+        self
+    }
+
+    pub fn boundary(mut self, boundary: Boundary) -> UniverseBuilder {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Places a named pattern with its top-left corner at `row`, `col` on top of the soup.
+    pub fn pattern(mut self, name: &str, row: u32, col: u32) -> UniverseBuilder {
+        self.patterns.push((name.to_string(), row, col));
+        self
+    }
+
+    pub fn build(&self) -> Result<Universe, Error> {
+        let seed = self.seed.unwrap_or_else(rng::entropy_seed);
+        let generator = Generator::new(self.rng, seed);
+        let mut universe = Universe::blank(self.width, self.height, generator)?;
+
+        universe.fill_random(self.density);
+        universe.set_boundary(self.boundary);
+        for (name, row, col) in self.patterns.iter() {
+            let pattern =
+                patterns::lookup(name).ok_or_else(|| Error::UnknownPattern(name.clone()))?;
+            universe.stamp(*row, *col, &pattern.cells);
+        }
+
+        universe.save_initial();
+        Ok(universe)
+    }
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> Self {
+        UniverseBuilder::new()
+    }
+}
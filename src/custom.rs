@@ -0,0 +1,115 @@
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::{Cell, Universe, ANCHOR, WALL};
+
+/// What a [`Rule`] gets to see about a cell's surroundings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NeighborInfo {
+    /// Live cells in the universe's neighborhood, see `set_neighborhood()`.
+    pub live: u8,
+    pub row: u32,
+    pub col: u32,
+}
+
+/// A two-state transition function for Rust callers to plug into a `Universe`
+/// with [`Universe::set_custom_rule`].
+///
+/// ```
+/// use wasm_game_of_life::{Cell, NeighborInfo, Rule, Universe};
+///
+/// // Every cell with exactly two live neighbors is alive next generation
+/// struct Pairs;
+///
+/// impl Rule for Pairs {
+///     fn next_state(&self, _cell: Cell, neighbors: NeighborInfo) -> Cell {
+///         if neighbors.live == 2 { Cell::Alive } else { Cell::Dead }
+///     }
+/// }
+///
+/// let mut universe = Universe::new_with_size(16, 16).unwrap();
+/// universe.set_custom_rule(Pairs);
+/// universe.tick();
+/// ```
+pub trait Rule {
+    /// The cell's state next generation. Defaults to Conway's Life.
+    fn next_state(&self, cell: Cell, neighbors: NeighborInfo) -> Cell {
+        match (cell, neighbors.live) {
+            (Cell::Alive, 2) | (_, 3) => Cell::Alive,
+            _ => Cell::Dead,
+        }
+    }
+}
+
+/// Conway's Life, B3/S23, as a [`Rule`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Conway;
+
+impl Rule for Conway {}
+
+// A rule written in JS as `(state, liveNeighbors) => state`
+struct Callback(js_sys::Function);
+
+impl Rule for Callback {
+    fn next_state(&self, cell: Cell, neighbors: NeighborInfo) -> Cell {
+        let state = JsValue::from(cell as u8);
+        let live = JsValue::from(neighbors.live);
+        match self.0.call2(&JsValue::NULL, &state, &live) {
+            Ok(next) if next.is_truthy() => Cell::Alive,
+            Ok(_) => Cell::Dead,
+            // A throwing callback leaves the cell as it was
+            Err(_) => cell,
+        }
+    }
+}
+
+impl Universe {
+    /// Runs `rule` on every cell from the next tick on, until another rule is set.
+    /// Walls and anchors still hold their cells dead and alive.
+    pub fn set_custom_rule<R: Rule + 'static>(&mut self, rule: R) {
+        self.engine = Engine::Custom(Rc::new(rule));
+    }
+
+    pub(crate) fn tick_custom(&mut self, rule: Rc<dyn Rule>) {
+        let mut next = self.back_buffer();
+        self.changed.clear();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let cell = self.cells[idx];
+                let next_cell = if self.flags[idx] & WALL != 0 {
+                    Cell::Dead
+                } else if self.flags[idx] & ANCHOR != 0 {
+                    Cell::Alive
+                } else {
+                    let live = self.get_live_neighbor_count(row, col);
+                    rule.next_state(cell, NeighborInfo { live, row, col })
+                };
+
+                if next_cell != cell {
+                    self.changed.push(row);
+                    self.changed.push(col);
+                }
+                next[idx] = next_cell;
+            }
+        }
+        self.swap_buffers(next);
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Lets JS decide every cell's fate: from the next tick on, `callback` is
+    /// called as `callback(state, liveNeighbors)` for each cell, with `state` 0 or
+    /// 1, and returns the next state, where anything truthy is alive.
+    /// Calls that throw leave the cell unchanged.
+    ///
+    /// Every cell costs a call across the wasm boundary each tick, which is far
+    /// slower than the built-in rules, so this suits small grids and trying out
+    /// rule ideas; settle on a rulestring with `set_rule()` for anything large.
+    pub fn set_rule_callback(&mut self, callback: js_sys::Function) {
+        self.set_custom_rule(Callback(callback));
+    }
+}
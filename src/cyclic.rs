@@ -0,0 +1,75 @@
+use wasm_bindgen::prelude::*;
+
+use crate::engine::{Engine, Neighborhood};
+use crate::{Error, Universe};
+
+/// A cyclic automaton: each cell advances from state `k` to `k + 1`, wrapping
+/// back to 0 after the last state, once `threshold` of its neighbors are already
+/// in the next state. Random soups organize into spirals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CyclicRule {
+    pub states: u8,
+    pub threshold: u8,
+}
+
+impl CyclicRule {
+    /// Reads Mirek's Cellebration notation as `rule()` writes it, e.g.
+    /// `"R1/T1/C16/NN"`: range 1, `T` the threshold, `C` the states and `N` the
+    /// neighborhood (`M`oore, von `N`eumann or `H`exagonal).
+    pub fn parse(rule: &str) -> Option<(CyclicRule, Neighborhood)> {
+        let fields: Vec<&str> = rule.trim().split('/').collect();
+        let [range, threshold, states, neighborhood] = fields[..] else {
+            return None;
+        };
+        if !range.eq_ignore_ascii_case("R1") {
+            return None;
+        }
+        let number = |field: &str, key: char| {
+            let digits = field.strip_prefix([key, key.to_ascii_lowercase()])?;
+            digits.parse::<u8>().ok()
+        };
+        let rule = CyclicRule {
+            threshold: number(threshold, 'T').filter(|&t| t > 0)?,
+            states: number(states, 'C').filter(|&s| s >= 2)?,
+        };
+        let neighborhood = match neighborhood.to_ascii_uppercase().as_str() {
+            "NM" => Neighborhood::Moore,
+            "NN" => Neighborhood::VonNeumann,
+            "NH" => Neighborhood::Hexagonal,
+            _ => return None,
+        };
+        Some((rule, neighborhood))
+    }
+
+    pub fn successor(self, state: u8) -> u8 {
+        (state + 1) % self.states
+    }
+
+    /// `ahead` holds the neighbors in the state after `state`.
+    pub fn next_state(self, state: u8, ahead: u8) -> u8 {
+        if ahead >= self.threshold {
+            self.successor(state)
+        } else {
+            state
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to a cyclic automaton with `states` states (2 to 255) that advance
+    /// once `threshold` neighbors are ahead, counting in the current neighborhood.
+    /// `16, 1` with the von Neumann neighborhood gives the classic spirals; fill the
+    /// grid with `randomize_states()` to start one.
+    pub fn set_cyclic_rule(&mut self, states: u8, threshold: u8) -> Result<(), Error> {
+        if states < 2 {
+            return Err(Error::InvalidRule(format!("{} states", states)));
+        }
+        if threshold == 0 {
+            return Err(Error::InvalidRule("threshold 0".to_string()));
+        }
+        self.engine = Engine::Cyclic(CyclicRule { states, threshold });
+        self.sync_states();
+        Ok(())
+    }
+}
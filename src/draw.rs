@@ -0,0 +1,232 @@
+use std::ops::Range;
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Universe};
+
+// One axis of a line: where it starts, which way it goes, how far, and the grid size
+struct Axis {
+    start: u32,
+    forward: bool,
+    length: u64,
+    limit: u32,
+}
+
+impl Axis {
+    fn new(from: u32, to: u32, limit: u32) -> Axis {
+        Axis {
+            start: from,
+            forward: to >= from,
+            length: u64::from(from.max(to) - from.min(to)),
+            limit,
+        }
+    }
+
+    // The coordinate `step` cells along, if it lies inside the grid
+    fn at(&self, step: u64) -> Option<u32> {
+        let start = u64::from(self.start);
+        let coord = if self.forward {
+            start + step
+        } else {
+            start.checked_sub(step)?
+        };
+        if coord < u64::from(self.limit) {
+            Some(coord as u32)
+        } else {
+            None
+        }
+    }
+
+    // The steps that stay inside the grid
+    fn visible(&self) -> Range<u64> {
+        let (start, limit) = (u64::from(self.start), u64::from(self.limit));
+        if self.forward {
+            if start >= limit {
+                return 0..0;
+            }
+            0..self.length.min(limit - 1 - start) + 1
+        } else if limit == 0 {
+            0..0
+        } else {
+            start.saturating_sub(limit - 1)..self.length.min(start) + 1
+        }
+    }
+}
+
+// Cells of a `height` x `width` grid on the line from (`r0`, `c0`) to (`r1`, `c1`).
+// Matches Bresenham's algorithm, but computes each point directly so only the
+// steps along the longer axis that fall inside the grid are visited.
+pub(crate) fn line_points(
+    (r0, c0): (u32, u32),
+    (r1, c1): (u32, u32),
+    height: u32,
+    width: u32,
+) -> Vec<(u32, u32)> {
+    let rows = Axis::new(r0, r1, height);
+    let cols = Axis::new(c0, c1, width);
+    let cols_major = cols.length >= rows.length;
+    let (major, minor) = if cols_major {
+        (&cols, &rows)
+    } else {
+        (&rows, &cols)
+    };
+
+    let length = u128::from(major.length.max(1));
+    major
+        .visible()
+        .filter_map(|step| {
+            let offset = (2 * u128::from(step) * u128::from(minor.length) + length) / (2 * length);
+            let a = major.at(step)?;
+            let b = minor.at(offset as u64)?;
+            Some(if cols_major { (b, a) } else { (a, b) })
+        })
+        .collect()
+}
+
+// The `x` the midpoint circle algorithm reaches at `y`: the smallest with
+// (x + 1/2)² >= radius² - y²
+fn circle_x(radius: u64, y: u64) -> u64 {
+    let target = 4 * (u128::from(radius) * u128::from(radius) - u128::from(y) * u128::from(y));
+    let root = target.isqrt();
+    let root = if root * root < target { root + 1 } else { root };
+    (root / 2) as u64
+}
+
+// The largest value in `low..=high` where `pred` holds, given it holds at `low` and
+// switches off at most once
+fn last_where(mut low: u64, mut high: u64, pred: impl Fn(u64) -> bool) -> u64 {
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if pred(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+impl Universe {
+    // Sets a horizontal run of cells from `left` to `right` inclusive, clipped to the grid
+    fn span(&mut self, row: i64, left: i64, right: i64, state: Cell) {
+        for col in left.max(0)..=right.min(self.width as i64 - 1) {
+            self.plot(row, col, state);
+        }
+    }
+
+    // Sets every cell of a rectangle, clipped to the grid, without recording an edit
+    pub(crate) fn fill_region(&mut self, row: u32, col: u32, height: u32, width: u32, state: Cell) {
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        for r in rows {
+            for c in cols.clone() {
+                let idx = self.get_index(r, c);
+                self.cells[idx] = state;
+            }
+        }
+    }
+
+    // Sets one cell, silently ignoring coordinates outside the grid
+    fn plot(&mut self, row: i64, col: i64, state: Cell) {
+        if row >= 0 && col >= 0 && row < self.height as i64 && col < self.width as i64 {
+            let idx = self.get_index(row as u32, col as u32);
+            self.cells[idx] = state;
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Paints a continuous line of cells from (`r0`, `c0`) to (`r1`, `c1`) using
+    /// Bresenham's algorithm. Parts outside the grid are skipped.
+    pub fn draw_line(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, state: Cell) {
+        self.record_edit();
+        for (row, col) in line_points((r0, c0), (r1, c1), self.height, self.width) {
+            let idx = self.get_index(row, col);
+            self.cells[idx] = state;
+        }
+    }
+
+    /// Paints the outline of a `height` x `width` rectangle whose top-left corner is
+    /// at `row`, `col`. Parts outside the grid are skipped.
+    pub fn draw_rect(&mut self, row: u32, col: u32, height: u32, width: u32, state: Cell) {
+        self.record_edit();
+        if height == 0 || width == 0 {
+            return;
+        }
+
+        let (top, left) = (row as i64, col as i64);
+        let bottom = top + height as i64 - 1;
+        let right = left + width as i64 - 1;
+        for c in left..=right.min(self.width as i64 - 1) {
+            self.plot(top, c, state);
+            self.plot(bottom, c, state);
+        }
+        for r in top..=bottom.min(self.height as i64 - 1) {
+            self.plot(r, left, state);
+            self.plot(r, right, state);
+        }
+    }
+
+    /// Sets every cell of a `height` x `width` rectangle to `state`.
+    pub fn fill_rect(&mut self, row: u32, col: u32, height: u32, width: u32, state: Cell) {
+        self.record_edit();
+        self.fill_region(row, col, height, width, state);
+    }
+
+    /// Paints a circle of `radius` centered on `row`, `col` with the midpoint
+    /// algorithm, either as an outline or `filled`. Parts outside the grid are skipped.
+    pub fn draw_circle(&mut self, row: u32, col: u32, radius: u32, state: Cell, filled: bool) {
+        self.record_edit();
+        let top = row.saturating_sub(radius);
+        if top >= self.height {
+            return;
+        }
+
+        // The midpoint algorithm walks one octant, plotting (y, x) and (x, y) with `x`
+        // shrinking as `y` grows; each grid row's cells are worked out from that directly
+        let radius = u64::from(radius);
+        let x_at = |y: u64| circle_x(radius, y);
+        let last_y = last_where(0, radius, |y| x_at(y) >= y);
+        let (center_row, center_col) = (i64::from(row), i64::from(col));
+        let bottom = (u64::from(row) + radius).min(u64::from(self.height) - 1);
+        for r in u64::from(top)..=bottom {
+            let d = (r as i64 - center_row).unsigned_abs();
+            if filled {
+                let half = if d <= last_y {
+                    x_at(d)
+                } else {
+                    last_where(0, last_y, |y| x_at(y) >= d)
+                } as i64;
+                self.span(r as i64, center_col - half, center_col + half, state);
+                continue;
+            }
+
+            if d <= last_y {
+                let x = x_at(d) as i64;
+                self.plot(r as i64, center_col - x, state);
+                self.plot(r as i64, center_col + x, state);
+            }
+            if d >= x_at(last_y) {
+                // The first `y` whose `x` has come down to `d`
+                let low = last_where(0, last_y, |y| y == 0 || x_at(y - 1) > d);
+                let high = last_where(0, last_y, |y| x_at(y) >= d);
+                if low <= high {
+                    let (low, high) = (low as i64, high as i64);
+                    self.span(r as i64, center_col + low, center_col + high, state);
+                    self.span(r as i64, center_col - high, center_col - low, state);
+                }
+            }
+        }
+    }
+
+    /// Flips every cell of a `height` x `width` rectangle between alive and dead.
+    pub fn invert_region(&mut self, row: u32, col: u32, height: u32, width: u32) {
+        self.record_edit();
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        for r in rows {
+            for c in cols.clone() {
+                let idx = self.get_index(r, c);
+                self.cells[idx].toggle();
+            }
+        }
+    }
+}
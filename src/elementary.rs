@@ -0,0 +1,101 @@
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::{Boundary, Cell, Universe};
+
+// A one-dimensional rule drawn row by row down the grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Elementary {
+    // Wolfram code: bit `n` is the next state of a cell whose left, own and right
+    // states spell `n` in binary
+    pub rule: u8,
+    // Row holding the newest generation
+    pub row: u32,
+}
+
+impl Universe {
+    // The lowest row holding live cells, taken as the newest generation
+    pub(crate) fn newest_row(&self) -> u32 {
+        self.cells
+            .chunks(self.width as usize)
+            .rposition(|row| row.contains(&Cell::Alive))
+            .unwrap_or(0) as u32
+    }
+
+    // Writes the next generation below the newest one, scrolling the grid up
+    // one row once the bottom is reached
+    pub(crate) fn tick_elementary(&mut self, ca: Elementary) {
+        // A resize that did not go through `remap()` may have cut the row off
+        let ca = if ca.row < self.height {
+            ca
+        } else {
+            Elementary {
+                row: self.newest_row(),
+                ..ca
+            }
+        };
+        let width = self.width as usize;
+        let source = ca.row as usize * width;
+        let current = self.cells[source..source + width].to_vec();
+        let alive = |col: i64| -> u8 {
+            let col = match self.boundary {
+                Boundary::Wrap => col.rem_euclid(width as i64),
+                Boundary::Fixed if col < 0 || col >= width as i64 => return 0,
+                Boundary::Fixed => col,
+            };
+            current[col as usize] as u8
+        };
+        let next: Vec<Cell> = (0..width as i64)
+            .map(|col| {
+                let pattern = alive(col - 1) << 2 | alive(col) << 1 | alive(col + 1);
+                if ca.rule & (1 << pattern) != 0 {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                }
+            })
+            .collect();
+
+        self.changed.clear();
+        let target = if ca.row + 1 < self.height {
+            ca.row + 1
+        } else {
+            // Every cell takes the value of the one beneath it, the bottom row the new
+            // generation, so compare those before scrolling
+            let last = self.cells.len() - width;
+            let below = self.cells[width..].iter().chain(&next);
+            for (idx, (then, now)) in self.cells.iter().zip(below).enumerate() {
+                if now != then {
+                    self.changed.push((idx / width) as u32);
+                    self.changed.push((idx % width) as u32);
+                }
+            }
+            self.cells.copy_within(width.., 0);
+            self.cells[last..].copy_from_slice(&next);
+            self.engine = Engine::Elementary(ca);
+            return;
+        };
+
+        let start = target as usize * width;
+        for (col, &cell) in next.iter().enumerate() {
+            if self.cells[start + col] != cell {
+                self.changed.push(target);
+                self.changed.push(col as u32);
+            }
+        }
+        self.cells[start..start + width].copy_from_slice(&next);
+        self.engine = Engine::Elementary(Elementary { row: target, ..ca });
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to a one-dimensional elementary automaton such as rule 30, 90 or
+    /// 110. The lowest row holding live cells is taken as the current generation,
+    /// and each `tick()` writes the next one beneath it, scrolling the grid up once
+    /// the bottom row is reached.
+    pub fn set_elementary_rule(&mut self, rule: u8) {
+        let row = self.newest_row();
+        self.engine = Engine::Elementary(Elementary { rule, row });
+    }
+}
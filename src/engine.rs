@@ -0,0 +1,307 @@
+use std::mem;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+use crate::ant::Langton;
+use crate::colored::ColoredRule;
+use crate::custom::Rule;
+use crate::cyclic::CyclicRule;
+use crate::elementary::Elementary;
+use crate::forest::ForestFire;
+use crate::hensel::IsotropicRule;
+use crate::kernel::KernelRule;
+use crate::ltl::LtlRule;
+use crate::rule::{GenerationsRule, LifeRule};
+use crate::ruletable::RuleTable;
+use crate::{Boundary, Cell, Error, Rng, Universe, ANCHOR, WALL};
+
+// Neighbor offsets as (row, col), clockwise from north like Golly's rule tables
+pub(crate) const MOORE: [(i32, i32); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+pub(crate) const VON_NEUMANN: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+// Hex neighbors when odd rows sit half a cell to the right of even rows
+const HEX_EVEN_ROW: [(i32, i32); 6] = [(-1, -1), (-1, 0), (0, 1), (1, 0), (1, -1), (0, -1)];
+const HEX_ODD_ROW: [(i32, i32); 6] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (0, -1)];
+
+/// Which nearby cells birth/survival and Generations rules count as neighbors.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighborhood {
+    /// The eight surrounding cells.
+    Moore = 0,
+    /// The four orthogonally adjacent cells.
+    VonNeumann = 1,
+    /// The six cells touching a hexagon, drawing odd rows shifted half a cell right.
+    /// Wrapping universes need an even height to keep the rows aligned.
+    Hexagonal = 2,
+}
+
+impl Neighborhood {
+    // Neighbor offsets for a cell in `row`; only the hex layout depends on it
+    pub(crate) fn offsets(self, row: u32) -> &'static [(i32, i32)] {
+        match self {
+            Neighborhood::Moore => &MOORE,
+            Neighborhood::VonNeumann => &VON_NEUMANN,
+            Neighborhood::Hexagonal if row.is_multiple_of(2) => &HEX_EVEN_ROW,
+            Neighborhood::Hexagonal => &HEX_ODD_ROW,
+        }
+    }
+}
+
+// The transition function `tick()` applies
+#[derive(Clone)]
+pub(crate) enum Engine {
+    // A birth/survival rule on the two-state cells
+    Life(LifeRule),
+    // A birth/survival rule on the arrangement of neighbors, not just their count
+    Isotropic(Box<IsotropicRule>),
+    // A birth/survival rule with decay states on the state layer
+    Generations(GenerationsRule),
+    // A birth/survival rule with colored live cells on the state layer
+    Colored(ColoredRule),
+    // A cyclic rule advancing through the states on the state layer
+    Cyclic(CyclicRule),
+    // Trees growing and burning on the state layer
+    ForestFire(ForestFire),
+    // A Larger than Life rule with a wide neighborhood, on the state layer
+    LargerThanLife(LtlRule),
+    // A weighted-kernel rule on the state layer
+    Kernel(Box<KernelRule>),
+    // A Golly rule table on the state layer
+    Table(Box<RuleTable>),
+    // Wireworld's electrons and wires on the state layer
+    Wireworld,
+    // Ants recoloring the state layer as they walk
+    Langton(Box<Langton>),
+    // A one-dimensional rule filling the two-state cells row by row
+    Elementary(Elementary),
+    // A two-state rule supplied through the `Rule` trait
+    Custom(Rc<dyn Rule>),
+}
+
+impl Engine {
+    fn state_count(&self) -> u32 {
+        match self {
+            Engine::Life(_) | Engine::Isotropic(_) | Engine::Elementary(_) | Engine::Custom(_) => 2,
+            Engine::Generations(rule) => rule.state_count(),
+            Engine::Colored(rule) => rule.colors as u32 + 1,
+            Engine::Cyclic(rule) => rule.states as u32,
+            Engine::ForestFire(_) => 3,
+            Engine::LargerThanLife(rule) => rule.state_count(),
+            Engine::Kernel(_) => 2,
+            Engine::Wireworld => 4,
+            Engine::Langton(langton) => langton.state_count(),
+            Engine::Table(table) => table.state_count(),
+        }
+    }
+}
+
+impl Universe {
+    // Brings the state layer in line with edits made through the two-state API:
+    // killed cells drop to state 0 and cells brought to life enter state 1, as do
+    // cells left in a state the current rule does not have
+    pub(crate) fn sync_states(&mut self) {
+        if self.states.len() != self.cells.len() {
+            self.states = vec![0; self.cells.len()];
+        }
+        let states = self.engine.state_count();
+        for (state, &cell) in self.states.iter_mut().zip(self.cells.iter()) {
+            match (cell, *state) {
+                (Cell::Dead, _) => *state = 0,
+                (Cell::Alive, 0) => *state = 1,
+                (Cell::Alive, s) if s as u32 >= states => *state = 1,
+                _ => {}
+            }
+        }
+    }
+
+    // Index of the neighbor at an offset, wrapping or `None` past a fixed edge
+    pub(crate) fn neighbor_index(&self, row: u32, col: u32, (dr, dc): (i32, i32)) -> Option<usize> {
+        let (height, width) = (self.height as i64, self.width as i64);
+        let (row, col) = (row as i64 + dr as i64, col as i64 + dc as i64);
+        let (row, col) = match self.boundary {
+            Boundary::Wrap => (row.rem_euclid(height), col.rem_euclid(width)),
+            Boundary::Fixed if row < 0 || col < 0 || row >= height || col >= width => return None,
+            Boundary::Fixed => (row, col),
+        };
+        Some(self.get_index(row as u32, col as u32))
+    }
+
+    // The state of the neighbor at an offset, 0 past a fixed edge
+    pub(crate) fn neighbor_state(&self, row: u32, col: u32, offset: (i32, i32)) -> u8 {
+        self.neighbor_index(row, col, offset)
+            .map_or(0, |idx| self.states[idx])
+    }
+
+    // One generation of a multi-state engine; walls stay at 0 and anchors at 1
+    pub(crate) fn tick_states(&mut self) {
+        self.sync_states();
+        let mut engine = mem::replace(&mut self.engine, Engine::Life(LifeRule::CONWAY));
+        let mut next = mem::take(&mut self.back_states);
+        next.clone_from(&self.states);
+        let mut neighbors = Vec::new();
+        let counts = match &engine {
+            Engine::LargerThanLife(rule) => self.ltl_counts(rule),
+            _ => Vec::new(),
+        };
+        let sums = match &engine {
+            Engine::Kernel(rule) => self.kernel_sums(rule),
+            _ => Vec::new(),
+        };
+        self.changed.clear();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let state = self.states[idx];
+                let next_state = if self.flags[idx] & WALL != 0 {
+                    0
+                } else if self.flags[idx] & ANCHOR != 0 {
+                    1
+                } else {
+                    match &mut engine {
+                        Engine::Life(_)
+                        | Engine::Isotropic(_)
+                        | Engine::Elementary(_)
+                        | Engine::Custom(_) => {
+                            unreachable!("two-state rules run on the cells")
+                        }
+                        Engine::Langton(_) => unreachable!("ants move in `tick_ants()`"),
+                        Engine::Generations(rule) => {
+                            let live = self
+                                .neighborhood
+                                .offsets(row)
+                                .iter()
+                                .filter(|&&offset| self.neighbor_state(row, col, offset) == 1)
+                                .count();
+                            rule.next_state(state, live as u8)
+                        }
+                        Engine::Colored(rule) => {
+                            neighbors.clear();
+                            neighbors.resize(rule.colors as usize + 1, 0);
+                            for &offset in self.neighborhood.offsets(row) {
+                                let color = self.neighbor_state(row, col, offset) as usize;
+                                if color != 0 {
+                                    neighbors[color] += 1;
+                                }
+                            }
+                            rule.next_state(state, &neighbors)
+                        }
+                        Engine::Cyclic(rule) => {
+                            let successor = rule.successor(state);
+                            let ahead = self
+                                .neighborhood
+                                .offsets(row)
+                                .iter()
+                                .filter(|&&offset| {
+                                    self.neighbor_state(row, col, offset) == successor
+                                })
+                                .count();
+                            rule.next_state(state, ahead as u8)
+                        }
+                        Engine::ForestFire(rule) => self.forest_state(*rule, row, col, state),
+                        Engine::LargerThanLife(rule) => rule.next_state(state, counts[idx]),
+                        Engine::Kernel(rule) => rule.next_state(state, sums[idx]),
+                        Engine::Wireworld => self.wireworld_state(row, col, state),
+                        Engine::Table(table) => {
+                            neighbors.clear();
+                            for &offset in table.offsets() {
+                                neighbors.push(self.neighbor_state(row, col, offset));
+                            }
+                            table.next_state(state, &neighbors)
+                        }
+                    }
+                };
+
+                if next_state != state {
+                    self.changed.push(row);
+                    self.changed.push(col);
+                }
+                next[idx] = next_state;
+            }
+        }
+
+        self.engine = engine;
+        self.back_states = mem::replace(&mut self.states, next);
+        for (cell, &state) in self.cells.iter_mut().zip(self.states.iter()) {
+            *cell = if state == 0 { Cell::Dead } else { Cell::Alive };
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Neighbor cells counted by birth/survival and Generations rules. Rule tables
+    /// and Larger than Life rules name their own neighborhood.
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+
+    /// Number of states a cell can take under the current rule; 2 for Life.
+    pub fn state_count(&self) -> u32 {
+        self.engine.state_count()
+    }
+
+    /// The state of a cell, 0 (dead) to `state_count() - 1`, or 0 outside the grid.
+    /// Under two-state rules this is 1 for a live cell.
+    pub fn state(&self, row: u32, col: u32) -> u8 {
+        match self.checked_index(row, col) {
+            Ok(idx) if self.cells[idx] == Cell::Dead => 0,
+            Ok(idx) => self
+                .states
+                .get(idx)
+                .copied()
+                .filter(|&s| s != 0)
+                .unwrap_or(1),
+            Err(_) => 0,
+        }
+    }
+
+    /// Puts a cell in `state`; any nonzero state counts as alive for `cells()`.
+    pub fn set_state(&mut self, row: u32, col: u32, state: u8) -> Result<(), Error> {
+        let idx = self.checked_index(row, col)?;
+        let states = self.state_count();
+        if state as u32 >= states {
+            return Err(Error::InvalidState { state, states });
+        }
+        self.record_edit();
+        self.sync_states();
+        self.states[idx] = state;
+        self.cells[idx] = if state == 0 { Cell::Dead } else { Cell::Alive };
+        Ok(())
+    }
+
+    /// Pointer to one state byte per cell, laid out like `cells()`. Read it again
+    /// after edits or ticks, since bringing the layer up to date may move it.
+    pub fn states(&mut self) -> *const u8 {
+        self.sync_states();
+        self.states.as_ptr()
+    }
+
+    /// Gives every cell a uniformly random state of the current rule, the usual
+    /// start for cyclic automata.
+    pub fn randomize_states(&mut self) {
+        self.record_edit();
+        self.sync_states();
+        let states = self.state_count() as u64;
+        for idx in 0..self.cells.len() {
+            let state = (self.rng.next_u64() % states) as u8;
+            self.states[idx] = state;
+            self.cells[idx] = if state == 0 { Cell::Dead } else { Cell::Alive };
+        }
+    }
+}
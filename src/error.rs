@@ -0,0 +1,106 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+/// Errors reported by fallible universe operations.
+///
+/// Converted into a JS `Error` when crossing the wasm boundary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Width or height is zero, or the total cell count does not fit in a `u32`.
+    InvalidSize { width: u32, height: u32 },
+    /// No pattern is registered under this name.
+    UnknownPattern(String),
+    /// A cell buffer does not match the universe dimensions.
+    CellCount { expected: usize, actual: usize },
+    /// Malformed pattern or grid text; `line` is 1-based.
+    Parse { line: usize, message: String },
+    /// The operation only works on square universes.
+    NotSquare { width: u32, height: u32 },
+    /// An RGBA buffer does not hold exactly four bytes per cell.
+    ImageSize { expected: usize, actual: usize },
+    /// A pattern of this size does not fit in the universe.
+    PatternTooLarge { width: u32, height: u32 },
+    /// Coordinates lie outside the grid.
+    OutOfBounds { row: u32, col: u32 },
+    /// A flat coordinate array has an odd number of values.
+    OddCoordinates(usize),
+    /// The operation needs at least one live cell.
+    NoLiveCells,
+    /// Not enough memory to allocate this many cells.
+    OutOfMemory { cells: usize },
+    /// The operation needs a selection; call `select()` first.
+    NoSelection,
+    /// The pattern did not settle into a still life, oscillator or spaceship in time.
+    Aperiodic { generations: u32 },
+    /// Saved binary or share data is truncated, corrupt or from an unknown version.
+    InvalidData(String),
+    /// The operation needs a recording; call `start_recording()` first.
+    NoRecording,
+    /// The rule has no such cell state.
+    InvalidState { state: u8, states: u32 },
+    /// A rulestring that could not be understood.
+    InvalidRule(String),
+    /// A kernel that is not an odd square of weights.
+    InvalidKernel { size: u32, weights: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidSize { width, height } => {
+                write!(f, "invalid universe size {}x{}", width, height)
+            }
+            Error::UnknownPattern(name) => write!(f, "unknown pattern \"{}\"", name),
+            Error::CellCount { expected, actual } => {
+                write!(f, "expected {} cells but got {}", expected, actual)
+            }
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::NotSquare { width, height } => {
+                write!(f, "universe must be square but is {}x{}", width, height)
+            }
+            Error::ImageSize { expected, actual } => write!(
+                f,
+                "expected {} bytes of RGBA data but got {}",
+                expected, actual
+            ),
+            Error::PatternTooLarge { width, height } => write!(
+                f,
+                "pattern of size {}x{} does not fit in the universe",
+                width, height
+            ),
+            Error::OutOfBounds { row, col } => {
+                write!(f, "cell ({}, {}) is outside the universe", row, col)
+            }
+            Error::OddCoordinates(len) => {
+                write!(f, "expected [row, col] pairs but got {} values", len)
+            }
+            Error::NoLiveCells => write!(f, "universe has no live cells"),
+            Error::OutOfMemory { cells } => write!(f, "could not allocate {} cells", cells),
+            Error::NoSelection => write!(f, "nothing is selected"),
+            Error::Aperiodic { generations } => {
+                write!(
+                    f,
+                    "pattern did not repeat within {} generations",
+                    generations
+                )
+            }
+            Error::InvalidData(message) => write!(f, "invalid saved data: {}", message),
+            Error::NoRecording => write!(f, "nothing has been recorded"),
+            Error::InvalidState { state, states } => write!(
+                f,
+                "state {} is not one of the rule's {} states",
+                state, states
+            ),
+            Error::InvalidRule(rule) => write!(f, "invalid rule \"{}\"", rule),
+            Error::InvalidKernel { size, weights } => write!(f, "expected an odd kernel size with size x size weights but got size {} and {} weights", size, weights),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for JsValue {
+    fn from(err: Error) -> JsValue {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
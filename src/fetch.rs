@@ -0,0 +1,53 @@
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::Universe;
+
+#[wasm_bindgen]
+extern "C" {
+    // The global `fetch()` of browsers, workers and Node 18+
+    #[wasm_bindgen(js_name = fetch)]
+    fn global_fetch(url: &str) -> Promise;
+}
+
+// Downloads `url` and resolves to its body as text
+async fn fetch_text(url: &str) -> Result<String, JsValue> {
+    let response = JsFuture::from(global_fetch(url)).await?;
+    if !Reflect::get(&response, &"ok".into())?.is_truthy() {
+        let status = Reflect::get(&response, &"status".into())?;
+        return Err(js_sys::Error::new(&format!(
+            "fetching {} failed with status {}",
+            url,
+            status.as_f64().unwrap_or(0.0)
+        ))
+        .into());
+    }
+
+    let text: Function = Reflect::get(&response, &"text".into())?.dyn_into()?;
+    let body: Promise = text.call0(&response)?.dyn_into()?;
+    JsFuture::from(body)
+        .await?
+        .as_string()
+        .ok_or_else(|| js_sys::Error::new("response body is not text").into())
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Downloads a pattern file in any format `load_any()` accepts and stamps it with
+    /// its top-left corner at `row`, `col`.
+    ///
+    /// Returns a `Promise` resolving to the updated universe. The universe is moved
+    /// into the call, so keep the result: `u = await u.load_pattern_from_url(...)`.
+    pub async fn load_pattern_from_url(
+        mut self,
+        url: String,
+        row: u32,
+        col: u32,
+    ) -> Result<Universe, JsValue> {
+        let text = fetch_text(&url).await?;
+        self.load_any(&text, row, col)?;
+        Ok(self)
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::Universe;
+
+// Cell states of the forest-fire model
+pub(crate) const EMPTY: u8 = 0;
+pub(crate) const TREE: u8 = 1;
+pub(crate) const BURNING: u8 = 2;
+
+/// The Drossel-Schwabl forest-fire model: fires burn out, spread to neighboring
+/// trees and are sparked by lightning, while trees regrow on empty ground.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ForestFire {
+    // Chances per tick of a tree growing on an empty cell and of lightning
+    // igniting a tree with no burning neighbors
+    pub growth: f64,
+    pub lightning: f64,
+}
+
+impl ForestFire {
+    /// Reads the rule as `rule()` writes it, e.g. `"ForestFire p=0.01 f=0.0001"`.
+    pub fn parse(rule: &str) -> Option<ForestFire> {
+        let mut words = rule.split_whitespace();
+        if !words.next()?.eq_ignore_ascii_case("ForestFire") {
+            return None;
+        }
+        let mut chance = |key: &str| {
+            let value: f64 = words.next()?.strip_prefix(key)?.parse().ok()?;
+            Some(value).filter(|p| (0.0..=1.0).contains(p))
+        };
+        let growth = chance("p=")?;
+        let lightning = chance("f=")?;
+        match words.next() {
+            Some(_) => None,
+            None => Some(ForestFire { growth, lightning }),
+        }
+    }
+}
+
+impl fmt::Display for ForestFire {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ForestFire p={} f={}", self.growth, self.lightning)
+    }
+}
+
+impl Universe {
+    pub(crate) fn forest_state(&mut self, rule: ForestFire, row: u32, col: u32, state: u8) -> u8 {
+        match state {
+            BURNING => EMPTY,
+            TREE => {
+                let offsets = self.neighborhood.offsets(row);
+                let spreading = offsets
+                    .iter()
+                    .any(|&offset| self.neighbor_state(row, col, offset) == BURNING);
+                if spreading || self.roll(rule.lightning) {
+                    BURNING
+                } else {
+                    TREE
+                }
+            }
+            _ if self.roll(rule.growth) => TREE,
+            _ => EMPTY,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to the forest-fire model with states 0 (empty), 1 (tree) and 2
+    /// (burning). Each tick a burning cell burns out, a tree next to a fire
+    /// catches, any other tree is struck by lightning with probability
+    /// `lightning`, and an empty cell grows a tree with probability `growth`.
+    /// Draws come from the universe's generator, so seeded runs repeat. Live cells
+    /// start as trees.
+    pub fn set_forest_fire(&mut self, growth: f64, lightning: f64) {
+        self.engine = Engine::ForestFire(ForestFire {
+            growth: growth.clamp(0.0, 1.0),
+            lightning: lightning.clamp(0.0, 1.0),
+        });
+        self.sync_states();
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::mem;
+
+use crate::engine::Engine;
+use crate::rule::LifeRule;
+use crate::{Cell, Error, Universe, ANCHOR, WALL};
+
+// Bit of each cell in a 3x3 neighborhood index, row by row, the cell itself at 4
+const CENTER: usize = 1 << 4;
+
+// Hensel's letters for each neighbor count up to 4, each with a representative
+// arrangement, in the same bit layout and letter order as Golly
+const LETTERS: [&[(char, usize)]; 5] = [
+    &[],
+    &[('c', 1), ('e', 2)],
+    &[
+        ('c', 5),
+        ('e', 10),
+        ('a', 3),
+        ('i', 40),
+        ('k', 33),
+        ('n', 68),
+    ],
+    &[
+        ('c', 69),
+        ('e', 42),
+        ('a', 11),
+        ('i', 7),
+        ('k', 98),
+        ('n', 13),
+        ('j', 14),
+        ('q', 70),
+        ('r', 41),
+        ('y', 97),
+    ],
+    &[
+        ('c', 325),
+        ('e', 170),
+        ('a', 15),
+        ('i', 45),
+        ('k', 99),
+        ('n', 71),
+        ('j', 106),
+        ('q', 102),
+        ('r', 43),
+        ('t', 101),
+        ('w', 105),
+        ('y', 78),
+        ('z', 108),
+    ],
+];
+
+// The eight rotations and reflections of a 3x3 neighborhood index
+fn symmetries(bits: usize) -> impl Iterator<Item = usize> {
+    (0..8).map(move |symmetry| {
+        (0..9)
+            .filter(|bit| bits & (1 << bit) != 0)
+            .map(|bit| {
+                let (mut row, mut col) = (bit / 3, bit % 3);
+                for _ in 0..symmetry % 4 {
+                    (row, col) = (col, 2 - row);
+                }
+                if symmetry >= 4 {
+                    col = 2 - col;
+                }
+                1 << (row * 3 + col)
+            })
+            .sum()
+    })
+}
+
+// Neighbor count and Hensel letter of a neighborhood, ignoring the center; more
+// than four neighbors take the letter of the empty cells' arrangement
+fn classify(bits: usize) -> (u32, Option<char>) {
+    let neighbors = bits & !CENTER;
+    let count = neighbors.count_ones();
+    let lookup = if count > 4 {
+        !neighbors & 0x1ff & !CENTER
+    } else {
+        neighbors
+    };
+    let letter = LETTERS[lookup.count_ones() as usize]
+        .iter()
+        .find(|&&(_, shape)| symmetries(shape).any(|shape| shape == lookup))
+        .map(|&(letter, _)| letter);
+    (count, letter)
+}
+
+/// An isotropic non-totalistic rule in Hensel notation, such as `B2-a/S12`:
+/// births and survivals depend on how the live neighbors are arranged, up to
+/// rotation and reflection, not just how many there are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct IsotropicRule {
+    name: String,
+    // Next state for every 3x3 neighborhood, indexed by its bits
+    table: Vec<Cell>,
+}
+
+impl IsotropicRule {
+    /// Reads `B../S..` sections in which each count may be followed by letters to
+    /// allow only those arrangements, or by `-` and letters to exclude them.
+    pub fn parse(rule: &str) -> Result<IsotropicRule, Error> {
+        let invalid = || Error::InvalidRule(rule.to_string());
+        // Allowed (count, letter) pairs for births and survivals; `None` allows
+        // every arrangement of that count
+        let mut allowed: [Vec<(u32, Option<char>)>; 2] = [Vec::new(), Vec::new()];
+        let mut section = None;
+        let mut count = None;
+        let mut excluding = false;
+
+        for c in rule.trim().chars() {
+            match c.to_ascii_lowercase() {
+                'b' => section = Some(0),
+                's' => section = Some(1),
+                '/' => {}
+                '0'..='8' => {
+                    let section = section.ok_or_else(invalid)?;
+                    let n = c.to_digit(10).unwrap();
+                    allowed[section].push((n, None));
+                    count = Some((section, n));
+                    excluding = false;
+                }
+                '-' => {
+                    let (section, n) = count.ok_or_else(invalid)?;
+                    excluding = true;
+                    // Start from every arrangement and strike the letters that follow
+                    allowed[section].retain(|&(m, _)| m != n);
+                    for &(letter, _) in LETTERS[n.min(8 - n) as usize] {
+                        allowed[section].push((n, Some(letter)));
+                    }
+                }
+                letter => {
+                    let (section, n) = count.ok_or_else(invalid)?;
+                    if !LETTERS[n.min(8 - n) as usize]
+                        .iter()
+                        .any(|&(known, _)| known == letter)
+                    {
+                        return Err(invalid());
+                    }
+                    if excluding {
+                        allowed[section].retain(|&entry| entry != (n, Some(letter)));
+                    } else {
+                        // The first letter narrows "every arrangement" down to itself
+                        allowed[section].retain(|&entry| entry != (n, None));
+                        allowed[section].push((n, Some(letter)));
+                    }
+                }
+            }
+        }
+
+        let table = (0..512)
+            .map(|bits| {
+                let section = (bits & CENTER != 0) as usize;
+                let (count, letter) = classify(bits);
+                let hit = allowed[section]
+                    .iter()
+                    .any(|&(n, l)| n == count && (l.is_none() || l == letter));
+                if hit {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                }
+            })
+            .collect();
+        Ok(IsotropicRule {
+            name: rule.trim().to_string(),
+            table,
+        })
+    }
+}
+
+impl fmt::Display for IsotropicRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Universe {
+    // The 3x3 neighborhood of a cell as bits, the cell itself at `CENTER`
+    fn neighborhood_bits(&self, row: u32, col: u32) -> usize {
+        let mut bits = 0;
+        for bit in 0..9 {
+            let offset = (bit / 3 - 1, bit % 3 - 1);
+            let live = match self.neighbor_index(row, col, offset) {
+                Some(idx) => self.live_value(idx) != 0,
+                None => false,
+            };
+            bits |= (live as usize) << bit;
+        }
+        bits
+    }
+
+    pub(crate) fn tick_isotropic(&mut self) {
+        // Borrow the rule's table for the tick instead of copying it
+        let engine = mem::replace(&mut self.engine, Engine::Life(LifeRule::CONWAY));
+        let Engine::Isotropic(rule) = &engine else {
+            unreachable!("only isotropic rules tick here");
+        };
+        let mut next = self.back_buffer();
+        self.changed.clear();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let cell = self.cells[idx];
+                let next_cell = if self.flags[idx] & WALL != 0 {
+                    Cell::Dead
+                } else if self.flags[idx] & ANCHOR != 0 {
+                    Cell::Alive
+                } else {
+                    rule.table[self.neighborhood_bits(row, col)]
+                };
+
+                if next_cell != cell {
+                    self.changed.push(row);
+                    self.changed.push(col);
+                }
+                next[idx] = next_cell;
+            }
+        }
+        self.swap_buffers(next);
+        self.engine = engine;
+    }
+}
@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+use std::mem;
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Snapshot, Universe};
+
+const DEFAULT_DEPTH: usize = 64;
+
+// Old values of the cells an edit changed, by index
+#[derive(Clone, Default)]
+struct Delta {
+    cells: Vec<(u32, Cell)>,
+    flags: Vec<(u32, u8)>,
+    tags: Vec<(u32, u8)>,
+    states: Vec<(u32, u8)>,
+}
+
+// A delta when the grid kept its shape, otherwise the whole previous grid
+#[derive(Clone)]
+enum Step {
+    Delta(Delta),
+    Full(Snapshot),
+}
+
+impl Delta {
+    // Keeps the first value logged for each index, which is the oldest
+    fn normalize(&mut self) {
+        fn dedup<T>(changes: &mut Vec<(u32, T)>) {
+            changes.sort_by_key(|&(i, _)| i);
+            changes.dedup_by_key(|&mut (i, _)| i);
+        }
+        dedup(&mut self.cells);
+        dedup(&mut self.flags);
+        dedup(&mut self.tags);
+        dedup(&mut self.states);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+            && self.flags.is_empty()
+            && self.tags.is_empty()
+            && self.states.is_empty()
+    }
+}
+
+// How the grid has changed since the last edit, undo or redo
+#[derive(Clone)]
+enum Since {
+    // Nothing to undo or redo, so nothing to keep track of
+    Untracked,
+    // Only through edits that logged the old values of the cells they set
+    Journal(Delta),
+    // In ways `base`, a copy of the grid back then, has to be diffed against
+    Base,
+}
+
+// Steps applied in order to revert (or re-apply) one edit, newest at the back.
+//
+// Single-cell edits log the values they overwrite; anything else, a fill or a tick,
+// is diffed lazily against a copy of the grid taken beforehand, so only the touched
+// cells are stored either way.
+#[derive(Clone)]
+pub(crate) struct History {
+    undo: VecDeque<Vec<Step>>,
+    redo: Vec<Vec<Step>>,
+    since: Since,
+    base: Option<Snapshot>,
+    pending: bool,
+    depth: usize,
+    grouping: bool,
+    group_recorded: bool,
+}
+
+impl History {
+    pub fn new() -> History {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            since: Since::Untracked,
+            base: None,
+            pending: false,
+            depth: DEFAULT_DEPTH,
+            grouping: false,
+            group_recorded: false,
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() + self.pending as usize > self.depth {
+            if self.undo.pop_front().is_none() {
+                self.pending = false;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.since = Since::Untracked;
+        self.base = None;
+        self.pending = false;
+    }
+}
+
+fn changes<T: Copy + PartialEq>(base: &[T], current: &[T]) -> Vec<(u32, T)> {
+    base.iter()
+        .zip(current)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(i, (&old, _))| (i as u32, old))
+        .collect()
+}
+
+// Swaps the stored values into the layer, leaving the replaced ones in the delta
+fn swap_in<T>(layer: &mut [T], changes: &mut [(u32, T)]) {
+    for (i, value) in changes.iter_mut() {
+        mem::swap(&mut layer[*i as usize], value);
+    }
+}
+
+impl Universe {
+    // Called by every editing method before it changes cells
+    pub(crate) fn record_edit(&mut self) {
+        self.activity.invalidate();
+        if self.history.depth == 0 {
+            return;
+        }
+        if self.history.grouping {
+            if self.history.group_recorded {
+                // The group may have begun with logged edits
+                self.protect_history();
+                return;
+            }
+            self.history.group_recorded = true;
+        }
+
+        self.commit_drift();
+        self.sync_history_base();
+        self.history.since = Since::Base;
+        self.start_pending_edit();
+    }
+
+    // Called instead of `record_edit()` by edits that only change the cells at
+    // `indices`, so the old values are logged rather than the grid copied
+    pub(crate) fn record_cells(&mut self, indices: &[usize]) {
+        self.activity.invalidate();
+        if self.history.depth == 0 {
+            return;
+        }
+        if self.history.grouping {
+            if self.history.group_recorded {
+                self.log_cells(indices);
+                return;
+            }
+            self.history.group_recorded = true;
+        }
+
+        self.commit_drift();
+        self.history.since = Since::Journal(Delta::default());
+        self.log_cells(indices);
+        self.start_pending_edit();
+    }
+
+    // Called before the grid changes without an edit being recorded, e.g. by a tick
+    pub(crate) fn unrecorded_change(&mut self) {
+        self.activity.invalidate();
+        self.protect_history();
+    }
+
+    // Makes sure changes that aren't logged can still be diffed: a journal is
+    // turned into a copy of the grid as it was before the logged edits
+    pub(crate) fn protect_history(&mut self) {
+        let history = &self.history;
+        let idle = history.undo.is_empty() && history.redo.is_empty() && !history.pending;
+        let delta = match mem::replace(&mut self.history.since, Since::Untracked) {
+            Since::Journal(delta) if !idle => delta,
+            Since::Journal(_) | Since::Untracked => return,
+            Since::Base => {
+                self.history.since = Since::Base;
+                return;
+            }
+        };
+
+        self.sync_history_base();
+        let base = self.history.base.as_mut().expect("base was just copied");
+        // Oldest values last, so they win
+        for &(i, cell) in delta.cells.iter().rev() {
+            base.cells[i as usize] = cell;
+        }
+        for (layer, changes) in [
+            (&mut base.flags, &delta.flags),
+            (&mut base.tags, &delta.tags),
+            (&mut base.states, &delta.states),
+        ] {
+            for &(i, value) in changes.iter().rev() {
+                layer[i as usize] = value;
+            }
+        }
+        self.history.since = Since::Base;
+    }
+
+    fn log_cells(&mut self, indices: &[usize]) {
+        if let Since::Journal(delta) = &mut self.history.since {
+            for &idx in indices {
+                let i = idx as u32;
+                delta.cells.push((i, self.cells[idx]));
+                for (layer, changes) in [
+                    (&self.flags, &mut delta.flags),
+                    (&self.tags, &mut delta.tags),
+                    (&self.states, &mut delta.states),
+                ] {
+                    if let Some(&value) = layer.get(idx) {
+                        changes.push((i, value));
+                    }
+                }
+            }
+        }
+    }
+
+    // Turns what changed since the last edit, undo or redo into a step: a new entry
+    // for a pending edit, otherwise part of the entry it drifted from
+    fn commit_drift(&mut self) {
+        let step = self.take_drift();
+        if self.history.pending {
+            self.history.undo.push_back(step.into_iter().collect());
+        } else if let (Some(step), Some(top)) = (step, self.history.undo.back_mut()) {
+            top.insert(0, step);
+        }
+    }
+
+    fn start_pending_edit(&mut self) {
+        self.history.pending = true;
+        self.history.trim();
+        self.history.redo.clear();
+    }
+
+    // The step that takes the grid back to how it was at the last edit, undo or
+    // redo, if it changed since
+    fn take_drift(&mut self) -> Option<Step> {
+        match mem::replace(&mut self.history.since, Since::Untracked) {
+            Since::Untracked => return None,
+            Since::Journal(mut delta) => {
+                delta.normalize();
+                return if delta.is_empty() {
+                    None
+                } else {
+                    Some(Step::Delta(delta))
+                };
+            }
+            Since::Base => {}
+        }
+        let base = self.history.base.as_ref()?;
+        let same_shape = base.width == self.width
+            && base.height == self.height
+            && base.flags.len() == self.flags.len()
+            && base.tags.len() == self.tags.len()
+            && base.states.len() == self.states.len();
+        if !same_shape {
+            return Some(Step::Full(base.clone()));
+        }
+        let delta = Delta {
+            cells: changes(&base.cells, &self.cells),
+            flags: changes(&base.flags, &self.flags),
+            tags: changes(&base.tags, &self.tags),
+            states: changes(&base.states, &self.states),
+        };
+        if delta.is_empty() {
+            None
+        } else {
+            Some(Step::Delta(delta))
+        }
+    }
+
+    // Copies the grid into `base`, reusing its buffers
+    fn sync_history_base(&mut self) {
+        match &mut self.history.base {
+            Some(base) => {
+                base.width = self.width;
+                base.height = self.height;
+                base.cells.clone_from(&self.cells);
+                base.flags.clone_from(&self.flags);
+                base.tags.clone_from(&self.tags);
+                base.states.clone_from(&self.states);
+            }
+            None => self.history.base = Some(self.snapshot()),
+        }
+    }
+
+    // Applies the steps in order, turning them into the steps that undo them
+    fn apply_steps(&mut self, steps: &mut [Step]) {
+        self.activity.invalidate();
+        for step in steps.iter_mut() {
+            match step {
+                Step::Delta(delta) => {
+                    swap_in(&mut self.cells, &mut delta.cells);
+                    swap_in(&mut self.flags, &mut delta.flags);
+                    swap_in(&mut self.tags, &mut delta.tags);
+                    swap_in(&mut self.states, &mut delta.states);
+                }
+                Step::Full(snapshot) => {
+                    mem::swap(&mut self.width, &mut snapshot.width);
+                    mem::swap(&mut self.height, &mut snapshot.height);
+                    mem::swap(&mut self.cells, &mut snapshot.cells);
+                    mem::swap(&mut self.flags, &mut snapshot.flags);
+                    mem::swap(&mut self.tags, &mut snapshot.tags);
+                    mem::swap(&mut self.states, &mut snapshot.states);
+                }
+            }
+        }
+        steps.reverse();
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Reverts the most recent edit. Returns `false` if there is nothing to undo.
+    ///
+    /// Only edits are recorded; generations advanced by `tick()` since the edit are
+    /// discarded along with it.
+    pub fn undo(&mut self) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        let drift = self.take_drift();
+        let mut steps = if self.history.pending {
+            Vec::new()
+        } else {
+            self.history.undo.pop_back().expect("checked by can_undo")
+        };
+        // Redoing this comes back to the drifted grid, so the edit redone after it
+        // has to start from there too
+        if let (Some(step), Some(below)) = (&drift, self.history.redo.last_mut()) {
+            below.insert(0, step.clone());
+        }
+        steps.splice(0..0, drift);
+        self.apply_steps(&mut steps);
+        self.history.redo.push(steps);
+        self.history.pending = false;
+        self.history.since = Since::Journal(Delta::default());
+        true
+    }
+
+    /// Re-applies the most recently undone edit. Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let mut steps = match self.history.redo.pop() {
+            Some(steps) => steps,
+            None => return false,
+        };
+        let drift = self.take_drift();
+        // Likewise, undoing this comes back to the drifted grid
+        if let (Some(step), Some(below)) = (&drift, self.history.undo.back_mut()) {
+            below.insert(0, step.clone());
+        }
+        steps.splice(0..0, drift);
+        self.apply_steps(&mut steps);
+        self.history.undo.push_back(steps);
+        self.history.trim();
+        self.history.since = Since::Journal(Delta::default());
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.pending || !self.history.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    /// Maximum number of edits kept for `undo()`; `0` turns recording off.
+    pub fn set_history_depth(&mut self, depth: u32) {
+        self.history.depth = depth as usize;
+        self.history.trim();
+        if depth == 0 {
+            self.history.clear();
+        }
+    }
+
+    pub fn history_depth(&self) -> u32 {
+        self.history.depth as u32
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Starts grouping edits, e.g. for a mouse drag, so one `undo()` reverts them all.
+    pub fn begin_edit_group(&mut self) {
+        self.history.grouping = true;
+        self.history.group_recorded = false;
+    }
+
+    pub fn end_edit_group(&mut self) {
+        self.history.grouping = false;
+    }
+}
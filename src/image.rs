@@ -0,0 +1,43 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{Cell, Error, Universe};
+
+#[wasm_bindgen]
+impl Universe {
+    /// Seeds a universe from RGBA pixels, e.g. `ctx.getImageData(...).data`.
+    ///
+    /// Pixels darker than `threshold` become live cells, so dark ink on a light
+    /// background maps to the demo's black-on-white rendering. Transparent pixels
+    /// are treated as white.
+    pub fn from_image(
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        threshold: u8,
+    ) -> Result<Universe, Error> {
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        let expected = universe.cells.len() * 4;
+        if rgba.len() != expected {
+            return Err(Error::ImageSize {
+                expected,
+                actual: rgba.len(),
+            });
+        }
+
+        for (cell, pixel) in universe.cells.iter_mut().zip(rgba.chunks_exact(4)) {
+            if luminance(pixel) < threshold as f64 {
+                *cell = Cell::Alive;
+            }
+        }
+        universe.save_initial();
+        Ok(universe)
+    }
+}
+
+// Rec. 601 luma of an RGBA pixel composited over white
+fn luminance(pixel: &[u8]) -> f64 {
+    let luma = 0.299 * pixel[0] as f64 + 0.587 * pixel[1] as f64 + 0.114 * pixel[2] as f64;
+    let alpha = pixel[3] as f64 / 255.0;
+    luma * alpha + 255.0 * (1.0 - alpha)
+}
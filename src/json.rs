@@ -0,0 +1,127 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{check_size, Boundary, Cell, Error, Neighborhood, Universe};
+
+// What a universe saves: the grid and its settings. History, the `reset()`
+// snapshot and the selection are left out; a loaded universe starts fresh.
+#[derive(Serialize, Deserialize)]
+struct UniverseData {
+    width: u32,
+    height: u32,
+    // One 0/1 byte per cell, far shorter in JSON than cell names
+    cells: Vec<u8>,
+    #[serde(default)]
+    flags: Vec<u8>,
+    #[serde(default)]
+    tags: Vec<u8>,
+    // The state layer of multi-state rules
+    #[serde(default)]
+    states: Vec<u8>,
+    boundary: Boundary,
+    // As written by `rule()`; missing from files saved before it was kept
+    #[serde(default)]
+    rule: Option<String>,
+    #[serde(default)]
+    neighborhood: Option<Neighborhood>,
+    #[serde(default)]
+    count_self: bool,
+    rng: Generator,
+}
+
+impl UniverseData {
+    fn into_universe(self) -> Result<Universe, Error> {
+        check_size(self.width, self.height)?;
+        let expected = (self.width * self.height) as usize;
+        check_layer(&self.cells, expected, false)?;
+        check_layer(&self.flags, expected, true)?;
+        check_layer(&self.tags, expected, true)?;
+        check_layer(&self.states, expected, true)?;
+
+        let mut universe = Universe::blank(self.width, self.height, self.rng)?;
+        for (cell, &state) in universe.cells.iter_mut().zip(self.cells.iter()) {
+            *cell = if state == 0 { Cell::Dead } else { Cell::Alive };
+        }
+        if !self.flags.is_empty() {
+            universe.flags = self.flags;
+        }
+        universe.tags = self.tags;
+        universe.boundary = self.boundary;
+        if let Some(rule) = self.rule {
+            let neighborhood = self.neighborhood.unwrap_or(Neighborhood::Moore);
+            universe.restore_rule(&rule, neighborhood, self.count_self)?;
+        }
+        universe.states = self.states;
+        universe.save_initial();
+        Ok(universe)
+    }
+}
+
+// Layers must hold one byte per cell; optional ones may also be empty
+fn check_layer(layer: &[u8], expected: usize, optional: bool) -> Result<(), Error> {
+    if layer.len() == expected || (optional && layer.is_empty()) {
+        Ok(())
+    } else {
+        Err(Error::CellCount {
+            expected,
+            actual: layer.len(),
+        })
+    }
+}
+
+impl Serialize for Universe {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        UniverseData {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(|&cell| cell as u8).collect(),
+            flags: self.flags.clone(),
+            tags: self.tags.clone(),
+            states: self.states.clone(),
+            boundary: self.boundary,
+            rule: Some(self.rule()),
+            neighborhood: Some(self.neighborhood),
+            count_self: self.count_self,
+            rng: self.rng.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Universe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        UniverseData::deserialize(deserializer)?
+            .into_universe()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// The grid, its layers, boundary, rule and generator state as JSON. Undo
+    /// history is not included. The rule is saved as `rule()` writes it, so rule
+    /// tables, kernels, Langton's ants and callbacks, which it only names, can't be
+    /// loaded back.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("universe serializes to JSON")
+    }
+
+    /// Restores a universe saved with `to_json()`. Fails with `InvalidRule` if the
+    /// saved rule is one `set_rule()` can't read.
+    pub fn from_json(json: &str) -> Result<Universe, Error> {
+        let data: UniverseData = serde_json::from_str(json).map_err(|err| Error::Parse {
+            line: err.line(),
+            message: err.to_string(),
+        })?;
+        // Bad layers are reported against the end of the document, where they're found
+        data.into_universe().map_err(|err| match err {
+            Error::InvalidRule(_) => err,
+            err => Error::Parse {
+                line: json.lines().count().max(1),
+                message: err.to_string(),
+            },
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::{Error, Universe};
+
+/// A weighted neighborhood: each cell sums the weights of the live cells under a
+/// square kernel centered on it, and thresholds decide birth and survival.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct KernelRule {
+    size: u32,
+    // Row-major, `size` x `size`; the middle weight applies to the cell itself
+    weights: Vec<i32>,
+    birth: (i32, i32),
+    survival: (i32, i32),
+}
+
+impl KernelRule {
+    pub fn next_state(&self, state: u8, sum: i32) -> u8 {
+        let within = |(low, high): (i32, i32)| low <= sum && sum <= high;
+        match state {
+            0 => within(self.birth) as u8,
+            _ => within(self.survival) as u8,
+        }
+    }
+}
+
+impl fmt::Display for KernelRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "kernel {}x{} B{}..{}/S{}..{}",
+            self.size, self.size, self.birth.0, self.birth.1, self.survival.0, self.survival.1
+        )
+    }
+}
+
+impl Universe {
+    // Kernel-weighted live cells around every cell, in row-major order
+    pub(crate) fn kernel_sums(&self, rule: &KernelRule) -> Vec<i32> {
+        let reach = (rule.size / 2) as i32;
+        let taps: Vec<((i32, i32), i32)> = rule
+            .weights
+            .iter()
+            .enumerate()
+            .filter(|&(_, &weight)| weight != 0)
+            .map(|(i, &weight)| {
+                let (dr, dc) = ((i as u32 / rule.size) as i32, (i as u32 % rule.size) as i32);
+                ((dr - reach, dc - reach), weight)
+            })
+            .collect();
+
+        let mut sums = Vec::with_capacity(self.states.len());
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let sum = taps
+                    .iter()
+                    .filter(|&&(offset, _)| self.neighbor_state(row, col, offset) == 1)
+                    .map(|&(_, weight)| weight)
+                    .sum();
+                sums.push(sum);
+            }
+        }
+        sums
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to a weighted-neighborhood rule. `weights` fills an odd `size` x `size`
+    /// kernel row by row, its middle entry weighing the cell itself. A dead cell is
+    /// born when the weighted sum of live cells lies in `birth_min..=birth_max`, and a
+    /// live one survives when it lies in `survival_min..=survival_max`.
+    ///
+    /// An all-ones 3x3 kernel with a zero middle, birth 3..=3 and survival 2..=3 is
+    /// Conway's Life; negative weights make anti-neighbors.
+    pub fn set_kernel(
+        &mut self,
+        size: u32,
+        weights: &[i32],
+        birth_min: i32,
+        birth_max: i32,
+        survival_min: i32,
+        survival_max: i32,
+    ) -> Result<(), Error> {
+        if size.is_multiple_of(2) || weights.len() as u64 != size as u64 * size as u64 {
+            return Err(Error::InvalidKernel {
+                size,
+                weights: weights.len(),
+            });
+        }
+        self.engine = Engine::Kernel(Box::new(KernelRule {
+            size,
+            weights: weights.to_vec(),
+            birth: (birth_min, birth_max),
+            survival: (survival_min, survival_max),
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::{Generator, Rng};
+use crate::{check_size, Error, RngKind};
+
+/// Continuous cellular automaton in the style of Lenia: every cell holds a value in
+/// `[0, 1]`, a smooth ring-shaped kernel averages its surroundings, and a bell-shaped
+/// growth function of that average nudges the value up or down each step.
+///
+/// The grid wraps around. The defaults (radius 13, growth 0.15 ± 0.015, step 0.1)
+/// support Lenia's orbium glider.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Lenia {
+    width: u32,
+    height: u32,
+    values: Vec<f32>,
+    radius: u32,
+    // Nonzero kernel weights by (row, col) offset, summing to 1
+    kernel: Vec<((i32, i32), f32)>,
+    mu: f32,
+    sigma: f32,
+    dt: f32,
+    generation: u32,
+}
+
+// Weights of a single smooth ring peaking halfway out to `radius`
+fn ring_kernel(radius: u32) -> Vec<((i32, i32), f32)> {
+    let reach = radius as i32;
+    let mut taps = Vec::new();
+    for dr in -reach..=reach {
+        for dc in -reach..=reach {
+            let distance = ((dr * dr + dc * dc) as f32).sqrt() / radius as f32;
+            if distance > 0.0 && distance < 1.0 {
+                let weight = (-((distance - 0.5) / 0.15).powi(2) / 2.0).exp();
+                taps.push(((dr, dc), weight));
+            }
+        }
+    }
+    let total: f32 = taps.iter().map(|&(_, weight)| weight).sum();
+    for tap in taps.iter_mut() {
+        tap.1 /= total;
+    }
+    taps
+}
+
+#[wasm_bindgen]
+impl Lenia {
+    /// Creates a `width` x `height` field with every value at 0.
+    pub fn new(width: u32, height: u32) -> Result<Lenia, Error> {
+        check_size(width, height)?;
+        Ok(Lenia {
+            width,
+            height,
+            values: vec![0.0; (width * height) as usize],
+            radius: 13,
+            kernel: ring_kernel(13),
+            mu: 0.15,
+            sigma: 0.015,
+            dt: 0.1,
+            generation: 0,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Radius of the kernel in cells; larger radii make smoother, slower creatures.
+    pub fn set_radius(&mut self, radius: u32) {
+        self.radius = radius.max(1);
+        self.kernel = ring_kernel(self.radius);
+    }
+
+    /// Centers the growth bell on a kernel average of `mu`, with width `sigma`.
+    pub fn set_growth(&mut self, mu: f32, sigma: f32) {
+        self.mu = mu;
+        self.sigma = sigma;
+    }
+
+    /// Fraction of the growth applied per step.
+    pub fn set_dt(&mut self, dt: f32) {
+        self.dt = dt;
+    }
+
+    /// The value of a cell, or 0 outside the field.
+    pub fn value(&self, row: u32, col: u32) -> f32 {
+        if row < self.height && col < self.width {
+            self.values[(row * self.width + col) as usize]
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets a cell, clamping `value` to `[0, 1]`. Coordinates outside the field are ignored.
+    pub fn set_value(&mut self, row: u32, col: u32, value: f32) {
+        if row < self.height && col < self.width {
+            self.values[(row * self.width + col) as usize] = value.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Fills the field with uniform random values, reproducible for the same `seed`.
+    pub fn randomize(&mut self, seed: u64) {
+        let mut rng = Generator::new(RngKind::XorShift, seed);
+        for value in self.values.iter_mut() {
+            *value = rng.next_f64() as f32;
+        }
+    }
+
+    /// Pointer to one `f32` per cell in row-major order.
+    pub fn values(&self) -> *const f32 {
+        self.values.as_ptr()
+    }
+
+    pub fn tick(&mut self) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut next = Vec::with_capacity(self.values.len());
+        for row in 0..height {
+            for col in 0..width {
+                let average: f32 = self
+                    .kernel
+                    .iter()
+                    .map(|&((dr, dc), weight)| {
+                        let r = (row + dr).rem_euclid(height);
+                        let c = (col + dc).rem_euclid(width);
+                        self.values[(r * width + c) as usize] * weight
+                    })
+                    .sum();
+                let growth =
+                    2.0 * (-(average - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp() - 1.0;
+                let value = self.values[(row * width + col) as usize];
+                next.push((value + self.dt * growth).clamp(0.0, 1.0));
+            }
+        }
+        self.values = next;
+        self.generation += 1;
+    }
+
+    /// The field as RGBA pixels for `ImageData`, shading from dark blue through
+    /// teal to pale yellow as values rise from 0 to 1.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        const LOW: [f32; 3] = [20.0, 20.0, 60.0];
+        const MID: [f32; 3] = [30.0, 160.0, 160.0];
+        const HIGH: [f32; 3] = [250.0, 240.0, 160.0];
+
+        let mut rgba = Vec::with_capacity(self.values.len() * 4);
+        for &value in self.values.iter() {
+            let (from, to, t) = if value < 0.5 {
+                (LOW, MID, value * 2.0)
+            } else {
+                (MID, HIGH, value * 2.0 - 1.0)
+            };
+            for channel in 0..3 {
+                rgba.push((from[channel] + (to[channel] - from[channel]) * t).round() as u8);
+            }
+            rgba.push(255);
+        }
+        rgba
+    }
+}
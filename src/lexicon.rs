@@ -0,0 +1,127 @@
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::Pattern;
+use crate::rle;
+
+// Curated Life Lexicon / LifeWiki objects as name, description and RLE
+const LEXICON: &[(&str, &str, &str)] = &[
+    (
+        "ship",
+        "Still life: a boat with an extra cell",
+        "x = 3, y = 3\n2o$obo$b2o!",
+    ),
+    (
+        "pond",
+        "Still life: an eight-cell ring",
+        "x = 4, y = 4\nb2o$o2bo$o2bo$b2o!",
+    ),
+    ("snake", "Still life", "x = 4, y = 2\n2obo$ob2o!"),
+    ("barge", "Still life", "x = 4, y = 4\nbo$obo$bobo$2bo!"),
+    ("long_boat", "Still life", "x = 4, y = 4\nbo$obo$bobo$2b2o!"),
+    (
+        "eater_1",
+        "Still life that eats gliders and other small objects",
+        "x = 4, y = 4\n2o$obo$2bo$2b2o!",
+    ),
+    (
+        "aircraft_carrier",
+        "Still life made of two hooks",
+        "x = 4, y = 3\n2o$o2bo$2b2o!",
+    ),
+    (
+        "clock",
+        "Period-2 oscillator",
+        "x = 4, y = 4\n2bo$obo$bobo$bo!",
+    ),
+    (
+        "koks_galaxy",
+        "Period-8 oscillator with four arms",
+        "x = 9, y = 9\n6ob2o$6ob2o$7b2o$2o5b2o$2o5b2o$2o5b2o$2o$2ob6o$2ob6o!",
+    ),
+    (
+        "tumbler",
+        "Period-14 oscillator",
+        "x = 9, y = 5\nbo5bo$obo3bobo$o2bobo2bo$2bo3bo$2b2ob2o!",
+    ),
+    (
+        "octagon_2",
+        "Period-5 oscillator",
+        "x = 8, y = 8\n3b2o$2bo2bo$bo4bo$o6bo$o6bo$bo4bo$2bo2bo$3b2o!",
+    ),
+    (
+        "figure_eight",
+        "Period-8 oscillator",
+        "x = 6, y = 6\n2o$2obo$4bo$bo$2bob2o$4b2o!",
+    ),
+    (
+        "copperhead",
+        "Period-10 orthogonal c/10 spaceship",
+        "x = 8, y = 12\nb2o2b2o$3b2o$3b2o$obo2bobo$o6bo2$o6bo$b2o2b2o$2b4o2$3b2o$3b2o!",
+    ),
+    (
+        "mold",
+        "Period-4 oscillator",
+        "x = 6, y = 6\n3b2o$2bo2bo$o2bobo$4bo$ob2o$bo!",
+    ),
+    (
+        "caterer",
+        "Period-3 oscillator",
+        "x = 8, y = 6\n2bo$o3b4o$o3bo$o$3bo$b2o!",
+    ),
+    (
+        "herschel",
+        "Heptomino that takes part in many glider-producing reactions",
+        "x = 3, y = 4\no$3o$obo$2bo!",
+    ),
+    (
+        "b_heptomino",
+        "Heptomino that stabilizes after 148 generations",
+        "x = 4, y = 3\nob2o$3o$bo!",
+    ),
+    (
+        "pi_heptomino",
+        "Heptomino that stabilizes after 173 generations",
+        "x = 3, y = 3\n3o$obo$obo!",
+    ),
+    (
+        "century",
+        "Heptomino that stabilizes after 103 generations",
+        "x = 4, y = 3\n2b2o$3o$bo!",
+    ),
+];
+
+// Decodes the lexicon entry `name`
+pub(crate) fn lookup(name: &str) -> Option<Pattern> {
+    LEXICON
+        .iter()
+        .find(|(entry, _, _)| *entry == name)
+        .map(|(_, _, text)| rle::parse(text).expect("lexicon RLE is valid"))
+}
+
+/// Names of the embedded lexicon patterns, for building a pattern picker. They can
+/// all be passed to `insert_pattern()`.
+#[wasm_bindgen]
+pub fn list_patterns() -> Vec<String> {
+    LEXICON
+        .iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect()
+}
+
+/// One-line description of a lexicon pattern.
+#[wasm_bindgen]
+pub fn lexicon_description(name: &str) -> Option<String> {
+    LEXICON
+        .iter()
+        .find(|(entry, _, _)| *entry == name)
+        .map(|(_, description, _)| description.to_string())
+}
+
+/// RLE text of a lexicon pattern, e.g. for copying to Golly.
+#[wasm_bindgen]
+pub fn lexicon_rle(name: &str) -> Option<String> {
+    LEXICON
+        .iter()
+        .find(|(entry, _, _)| *entry == name)
+        .map(|(_, _, text)| text.to_string())
+}
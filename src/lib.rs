@@ -1,8 +1,94 @@
+mod activity;
+mod ant;
+mod apgcode;
+mod binary;
+mod builder;
+mod colored;
+mod custom;
+mod cyclic;
+mod draw;
+mod elementary;
+mod engine;
+mod error;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod font;
+mod forest;
+mod hensel;
+mod history;
+mod image;
+#[cfg(feature = "serde")]
+mod json;
+mod kernel;
+mod lenia;
+#[cfg(feature = "lexicon")]
+mod lexicon;
+mod life105;
+mod life106;
+mod life3d;
+mod ltl;
+mod macrocell;
+mod noise;
+mod packed;
+mod pattern;
+mod patterns;
+mod plaintext;
+mod region;
+mod replay;
+mod rle;
+mod rng;
+mod rule;
+mod ruletable;
+mod schedule;
+mod search;
+mod selection;
+mod share;
+mod soup;
+mod sparse;
+mod stochastic;
+mod teams;
+mod text;
+mod transform;
 mod utils;
+mod wireworld;
+mod zone;
 
 use std::fmt;
+use std::mem;
+use std::ops::{Index, IndexMut, Range};
 use wasm_bindgen::prelude::*;
 
+use activity::{Active, Activity, TILE};
+pub use ant::Ant;
+pub use builder::UniverseBuilder;
+pub use custom::{Conway, NeighborInfo, Rule};
+use engine::Engine;
+pub use engine::Neighborhood;
+pub use error::Error;
+use history::History;
+pub use lenia::Lenia;
+#[cfg(feature = "lexicon")]
+pub use lexicon::{lexicon_description, lexicon_rle, list_patterns};
+pub use life3d::Life3d;
+pub use packed::PackedUniverse;
+pub use pattern::{Orientation, Pattern};
+pub use patterns::{pattern_names, register_pattern, unregister_pattern, Direction};
+pub use region::BlendMode;
+pub use replay::Replay;
+use replay::{Playback, Recording};
+use rng::Generator;
+pub use rng::{MathRandom, Pcg32, Rng, RngKind, XorShift64};
+use rule::LifeRule;
+pub use rule::RulePreset;
+use schedule::Schedule;
+use selection::Selection;
+pub use soup::{Gradient, Symmetry};
+pub use sparse::SparseUniverse;
+pub use teams::Team;
+pub use transform::Anchor;
+pub use wireworld::WireState;
+use zone::Zone;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
@@ -12,94 +98,535 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 #[wasm_bindgen]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cell {
     Dead = 0,
     Alive = 1,
 }
 
+impl Cell {
+    fn toggle(&mut self) {
+        *self = match *self {
+            Cell::Dead => Cell::Alive,
+            Cell::Alive => Cell::Dead,
+        };
+    }
+}
+
+/// What neighbor counting sees past the edge of the grid.
 #[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Boundary {
+    /// Edges wrap around, making the universe a torus.
+    Wrap = 0,
+    /// Everything outside the grid is permanently dead.
+    Fixed = 1,
+}
+
+// Bits of the per-cell flag layer returned by `flags()`
+pub const WALL: u8 = 1;
+pub const ANCHOR: u8 = 2;
+
+// Saved grid for `reset()` and the edit history
+#[derive(Clone)]
+struct Snapshot {
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+    flags: Vec<u8>,
+    tags: Vec<u8>,
+    states: Vec<u8>,
+    rng: Generator,
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: Vec<Cell>,
+    // Spare buffer the next tick writes into before the two trade places
+    back: Vec<Cell>,
+    // What the last tick left behind, to limit the next one to active cells
+    activity: Activity,
+    // Per-cell flag bits such as `WALL`, parallel to `cells`
+    flags: Vec<u8>,
+    // Optional annotation byte per cell, empty until the first tag is set
+    tags: Vec<u8>,
+    // State of every cell under a multi-state rule, empty until one is used
+    states: Vec<u8>,
+    // Spare state layer multi-state ticks write into, like `back`
+    back_states: Vec<u8>,
+    boundary: Boundary,
+    engine: Engine,
+    neighborhood: Neighborhood,
+    // Whether birth/survival rules count a live cell among its own neighbors
+    count_self: bool,
+    // Chances that births and survivals called for by a birth/survival rule happen
+    birth_chance: f64,
+    survival_chance: f64,
+    // Chance of each cell flipping after a tick
+    noise: f64,
+    // Rules taking turns, set up with `add_scheduled_rule()`
+    schedule: Option<Schedule>,
+    // Rectangles running their own Life-like rule, set up with `add_rule_zone()`
+    zones: Vec<Zone>,
+    rng: Generator,
+    initial: Snapshot,
+    // Flat [row, col, ...] list of cells flipped by the last tick
+    changed: Vec<u32>,
+    history: History,
+    selection: Option<Selection>,
+    recording: Option<Recording>,
+    playback: Option<Playback>,
 }
 
 impl Universe {
+    fn blank(width: u32, height: u32, rng: Generator) -> Result<Universe, Error> {
+        utils::set_panic_hook();
+        check_size(width, height)?;
+
+        let cells = vec![Cell::Dead; (width * height) as usize];
+        let flags = vec![0; cells.len()];
+        Ok(Universe {
+            width,
+            height,
+            cells: cells.clone(),
+            back: Vec::new(),
+            activity: Activity::new(),
+            flags: flags.clone(),
+            tags: Vec::new(),
+            states: Vec::new(),
+            back_states: Vec::new(),
+            boundary: Boundary::Wrap,
+            engine: Engine::Life(LifeRule::CONWAY),
+            neighborhood: Neighborhood::Moore,
+            count_self: false,
+            birth_chance: 1.0,
+            survival_chance: 1.0,
+            noise: 0.0,
+            schedule: None,
+            zones: Vec::new(),
+            rng: rng.clone(),
+            changed: Vec::new(),
+            history: History::new(),
+            selection: None,
+            recording: None,
+            playback: None,
+            initial: Snapshot {
+                width,
+                height,
+                cells,
+                flags,
+                tags: Vec::new(),
+                states: Vec::new(),
+                rng,
+            },
+        })
+    }
+
     fn get_index(&self, row: u32, column: u32) -> usize {
         (row * self.width + column) as usize
     }
 
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.clone(),
+            flags: self.flags.clone(),
+            tags: self.tags.clone(),
+            states: self.states.clone(),
+            rng: self.rng.clone(),
+        }
+    }
+
+    // Restores the grid from a snapshot, reusing the cell buffer; the generator is left alone
+    fn restore(&mut self, snapshot: &Snapshot) {
+        self.unrecorded_change();
+        self.cells.clear();
+        self.cells.extend_from_slice(&snapshot.cells);
+        self.flags.clear();
+        self.flags.extend_from_slice(&snapshot.flags);
+        self.tags.clear();
+        self.tags.extend_from_slice(&snapshot.tags);
+        self.states.clear();
+        self.states.extend_from_slice(&snapshot.states);
+        self.width = snapshot.width;
+        self.height = snapshot.height;
+    }
+
+    // Refills every cell from the generator without recording an edit
+    fn fill_random(&mut self, density: f64) {
+        for cell in self.cells.iter_mut() {
+            *cell = if self.rng.next_f64() < density {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+        }
+    }
+
+    fn checked_index(&self, row: u32, column: u32) -> Result<usize, Error> {
+        if row < self.height && column < self.width {
+            Ok(self.get_index(row, column))
+        } else {
+            Err(Error::OutOfBounds { row, col: column })
+        }
+    }
+
+    // Checked indices for a flat [row0, col0, row1, col1, ...] array
+    fn pair_indices(&self, coords: &[u32]) -> Result<Vec<usize>, Error> {
+        if !coords.len().is_multiple_of(2) {
+            return Err(Error::OddCoordinates(coords.len()));
+        }
+        coords
+            .chunks_exact(2)
+            .map(|pair| self.checked_index(pair[0], pair[1]))
+            .collect()
+    }
+
     fn get_live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        if self.neighborhood != Neighborhood::Moore {
+            return self
+                .neighborhood
+                .offsets(row)
+                .iter()
+                .filter_map(|&offset| self.neighbor_index(row, column, offset))
+                .map(|idx| self.live_value(idx))
+                .sum();
+        }
+        if self.boundary == Boundary::Fixed {
+            return self.get_bounded_neighbor_count(row, column);
+        }
+
+        // Rows and columns on either side, wrapping around at the edges, each with
+        // whether it is the same offset as the cell itself
+        let above = if row == 0 { self.height - 1 } else { row - 1 };
+        let below = if row + 1 == self.height { 0 } else { row + 1 };
+        let left = if column == 0 {
+            self.width - 1
+        } else {
+            column - 1
+        };
+        let right = if column + 1 == self.width {
+            0
+        } else {
+            column + 1
+        };
+        let rows = [(above, self.height == 1), (row, true), (below, false)];
+        let cols = [(left, self.width == 1), (column, true), (right, false)];
+
+        let mut count = 0;
+        for (neighbor_row, same_row) in rows {
+            for (neighbor_col, same_col) in cols {
+                if same_row && same_col {
+                    continue;
+                }
+                let idx = self.get_index(neighbor_row, neighbor_col);
+                count += self.live_value(idx);
+            }
+        }
+
+        count
+    }
+
+    // 1 for a live cell that takes part in the rules, 0 otherwise: anchors count
+    // and walls do not. Computed without branches, as the neighbor counts call
+    // this for every cell every tick.
+    fn live_value(&self, idx: usize) -> u8 {
+        let flags = self.flags[idx];
+        // `WALL` is the lowest bit, so this keeps a live cell's 1 unless it is a wall
+        let alive = self.cells[idx] as u8 & !flags & WALL;
+        alive | (flags & ANCHOR != 0) as u8
+    }
+
+    // Sets or clears one flag bit; walls and anchors exclude each other and force the cell state
+    fn set_flag(&mut self, row: u32, col: u32, flag: u8, on: bool) -> Result<(), Error> {
+        let idx = self.checked_index(row, col)?;
+        self.record_cells(&[idx]);
+        if on {
+            self.flags[idx] = (self.flags[idx] & !(WALL | ANCHOR)) | flag;
+            self.cells[idx] = if flag == ANCHOR {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+        } else {
+            self.flags[idx] &= !flag;
+        }
+        Ok(())
+    }
+
+    fn clear_flag(&mut self, flag: u8) {
+        self.record_edit();
+        for flags in self.flags.iter_mut() {
+            *flags &= !flag;
+        }
+    }
+
+    fn get_bounded_neighbor_count(&self, row: u32, column: u32) -> u8 {
         let mut count = 0;
 
-        // Iterate through 8 neighbors using wrap-around to hit cells to the left or above current
-        for delta_row in [self.height - 1, 0, 1].iter().cloned() {
-            for delta_col in [self.width - 1, 0, 1].iter().cloned() {
-                if delta_row == 0 && delta_col == 0 {
+        // Only visit neighbors that are inside the grid
+        for neighbor_row in row.saturating_sub(1)..=(row + 1).min(self.height - 1) {
+            for neighbor_col in column.saturating_sub(1)..=(column + 1).min(self.width - 1) {
+                if neighbor_row == row && neighbor_col == column {
                     continue;
                 }
 
-                let neighbor_row = (row + delta_row) % self.width;
-                let neighbor_col = (column + delta_col) % self.height;
                 let idx = self.get_index(neighbor_row, neighbor_col);
-                count += self.cells[idx] as u8;
+                count += self.live_value(idx);
             }
         }
 
         count
     }
 
+    // Rows and columns of a rectangle, clipped to the grid
+    fn clip_region(&self, row: u32, col: u32, height: u32, width: u32) -> (Range<u32>, Range<u32>) {
+        let rows = row.min(self.height)..row.saturating_add(height).min(self.height);
+        let cols = col.min(self.width)..col.saturating_add(width).min(self.width);
+        (rows, cols)
+    }
+
+    // Sets cells alive relative to an origin, wrapping past the edges
+    fn stamp(&mut self, row: u32, column: u32, cells: &[(u32, u32)]) {
+        for &(delta_row, delta_col) in cells {
+            let idx = self.wrapped_index(row, column, delta_row, delta_col);
+            self.cells[idx] = Cell::Alive;
+        }
+    }
+
+    // Index of an offset from an origin, wrapping past the edges without overflowing
+    fn wrapped_index(&self, row: u32, column: u32, delta_row: u32, delta_col: u32) -> usize {
+        let row = (row as u64 + delta_row as u64) % self.height as u64;
+        let column = (column as u64 + delta_col as u64) % self.width as u64;
+        self.get_index(row as u32, column as u32)
+    }
+
+    // Resets the buffer to all dead cells for the current size, reusing its allocation
+    fn fill_dead(&mut self) {
+        self.unrecorded_change();
+        self.cells.clear();
+        self.cells
+            .resize((self.width * self.height) as usize, Cell::Dead);
+        self.flags.clear();
+        self.flags.resize(self.cells.len(), 0);
+        self.tags.clear();
+        self.states.clear();
+    }
+
     pub fn set_width(&mut self, width: u32) {
         self.width = width;
-        let cells = (0..self.height * width).map(|_| Cell::Dead).collect();
-        self.cells = cells;
+        self.fill_dead();
     }
 
     pub fn set_height(&mut self, height: u32) {
         self.height = height;
-        let cells = (0..height * self.width).map(|_| Cell::Dead).collect();
-        self.cells = cells;
+        self.fill_dead();
     }
 
     pub fn get_cells(&self) -> &[Cell] {
         &self.cells
     }
 
+    /// Every cell as `(row, col, state)` in row-major order.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (u32, u32, Cell)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, &cell)| (idx as u32 / width, idx as u32 % width, cell))
+    }
+
+    /// Coordinates of the live cells in row-major order.
+    pub fn iter_live_cells(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.iter_cells()
+            .filter(|&(_, _, cell)| cell == Cell::Alive)
+            .map(|(row, col, _)| (row, col))
+    }
+
+    /// Sets the given cells alive.
+    ///
+    /// Panics if a coordinate is outside the grid; JS callers should use the
+    /// checked `set_cells` export (`set_cells_flat` in Rust) instead.
     pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.unrecorded_change();
         for (row, col) in cells.iter().cloned() {
             let idx = self.get_index(row, col);
             self.cells[idx] = Cell::Alive;
         }
     }
+
+    // The spare cell buffer sized to the grid, for a tick that writes every cell
+    pub(crate) fn back_buffer(&mut self) -> Vec<Cell> {
+        let mut next = mem::take(&mut self.back);
+        if next.len() != self.cells.len() {
+            next.clear();
+            // Keep the reserved capacity so resizes leave either buffer in place
+            next.reserve(self.cells.capacity());
+            next.resize(self.cells.len(), Cell::Dead);
+        }
+        next
+    }
+
+    // Makes a filled back buffer the current cells, keeping the old ones as spare
+    pub(crate) fn swap_buffers(&mut self, next: Vec<Cell>) {
+        self.back = mem::replace(&mut self.cells, next);
+    }
+
+    // One generation of a birth/survival rule on the two-state cells
+    fn tick_life(&mut self, rule: LifeRule) {
+        let lookups = self.zone_lookups(rule);
+        let active = self.active_region(rule);
+        let (across, down) = self.tile_counts();
+        self.activity.begin_tick((across * down) as usize);
+        let mut next = self.back_buffer();
+
+        match active {
+            // Only cells next to last tick's changes can change, so bring the spare
+            // buffer up to date and recompute just those
+            Some(active) => {
+                for pair in self.activity.changed.chunks_exact(2) {
+                    let idx = self.get_index(pair[0], pair[1]);
+                    next[idx] = self.cells[idx];
+                }
+                self.changed.clear();
+                match active {
+                    Active::Cells(cells) => {
+                        for idx in cells {
+                            let (row, col) = (idx as u32 / self.width, idx as u32 % self.width);
+                            next[idx] = self.next_life_cell(&lookups, row, col);
+                        }
+                    }
+                    // Row by row across the active tiles, keeping `changed` in order
+                    Active::Tiles(tiles) => {
+                        for row in 0..self.height {
+                            let tile_row = &tiles[(row / TILE * across) as usize..];
+                            for (tile, _) in tile_row
+                                .iter()
+                                .take(across as usize)
+                                .enumerate()
+                                .filter(|(_, &on)| on)
+                            {
+                                let left = tile as u32 * TILE;
+                                for col in left..(left + TILE).min(self.width) {
+                                    let idx = self.get_index(row, col);
+                                    next[idx] = self.next_life_cell(&lookups, row, col);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                self.changed.clear();
+                for row in 0..self.height {
+                    for col in 0..self.width {
+                        let idx = self.get_index(row, col);
+                        next[idx] = self.next_life_cell(&lookups, row, col);
+                    }
+                }
+            }
+        }
+
+        self.swap_buffers(next);
+    }
+
+    // The next state of one cell under a birth/survival rule, noting it in
+    // `changed` if it differs
+    fn next_life_cell(&mut self, lookups: &[[[Cell; 10]; 2]], row: u32, col: u32) -> Cell {
+        let idx = self.get_index(row, col);
+        let cell = self.cells[idx];
+        let live_neighbors =
+            self.get_live_neighbor_count(row, col) + (self.count_self && cell == Cell::Alive) as u8;
+        let lookup = &lookups[self.zone_index(row, col)];
+        let ruled = lookup[cell as usize][live_neighbors as usize];
+
+        let next_cell = match (cell, ruled) {
+            _ if self.flags[idx] & WALL != 0 => Cell::Dead, // Walls never come alive
+            _ if self.flags[idx] & ANCHOR != 0 => Cell::Alive, // Anchors never die
+            // Survival (S23 in Conway's Life) and birth (B3), when the dice allow
+            (Cell::Alive, Cell::Alive) if !self.roll(self.survival_chance) => Cell::Dead,
+            (Cell::Dead, Cell::Alive) if !self.roll(self.birth_chance) => Cell::Dead,
+            _ => ruled, // otherwise as the rule says
+        };
+
+        if next_cell != cell {
+            self.changed.push(row);
+            self.changed.push(col);
+            let tile = self.tile_index(row, col);
+            self.activity.mark(tile);
+        }
+        next_cell
+    }
 }
 
 // Public methods for Javascript export
 #[wasm_bindgen]
 impl Universe {
     pub fn new() -> Universe {
-        let width = 64;
-        let height = 64;
-        let cells = (0..width * height)
-            .map(|_| {
-                let v = js_sys::Math::random();
-                if v > 0.5 {
-                    Cell::Alive
-                } else {
-                    Cell::Dead
-                }
-            })
-            .collect();
-
-        let mut universe = Universe {
-            width,
-            height,
-            cells,
-        };
-
+        let mut universe = Universe::new_with_size(64, 64).unwrap();
         universe.generate_glider();
+        universe.save_initial();
         universe
     }
 
+    /// Creates a `width` x `height` universe filled with random soup.
+    pub fn new_with_size(width: u32, height: u32) -> Result<Universe, Error> {
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        universe.fill_random(0.5);
+        universe.save_initial();
+        Ok(universe)
+    }
+
+    /// Creates a random soup that is identical for every run with the same `seed`.
+    pub fn new_with_seed(width: u32, height: u32, seed: u64) -> Result<Universe, Error> {
+        Universe::new_with_rng(width, height, RngKind::XorShift, seed)
+    }
+
+    /// Like `new_with_seed`, but drawing from the chosen generator.
+    pub fn new_with_rng(
+        width: u32,
+        height: u32,
+        kind: RngKind,
+        seed: u64,
+    ) -> Result<Universe, Error> {
+        let mut universe = Universe::blank(width, height, Generator::new(kind, seed))?;
+        universe.fill_random(0.5);
+        universe.save_initial();
+        Ok(universe)
+    }
+
+    /// Creates a `width` x `height` universe with every cell dead.
+    pub fn new_empty(width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::blank(width, height, Generator::from_entropy())
+    }
+
+    /// Restores a universe from a flat row-major buffer, as returned by reading `cells()`.
+    ///
+    /// Any nonzero byte is treated as a live cell.
+    pub fn from_cells(width: u32, height: u32, cells: &[u8]) -> Result<Universe, Error> {
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        if cells.len() != universe.cells.len() {
+            return Err(Error::CellCount {
+                expected: universe.cells.len(),
+                actual: cells.len(),
+            });
+        }
+
+        for (cell, &value) in universe.cells.iter_mut().zip(cells) {
+            if value != 0 {
+                *cell = Cell::Alive;
+            }
+        }
+        universe.save_initial();
+        Ok(universe)
+    }
+
     pub fn render(&self) -> String {
         self.to_string()
     }
@@ -112,38 +639,313 @@ impl Universe {
         self.height
     }
 
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
+    pub fn rng_kind(&self) -> RngKind {
+        self.rng.kind()
+    }
+
+    /// Replaces the random generator, e.g. to make later `randomize()` calls reproducible.
+    pub fn set_rng(&mut self, kind: RngKind, seed: u64) {
+        self.rng = Generator::new(kind, seed);
+    }
+
+    pub fn get_cell(&self, row: u32, col: u32) -> Result<Cell, Error> {
+        Ok(self.cells[self.checked_index(row, col)?])
+    }
+
+    /// Whether a cell is alive; coordinates outside the grid count as dead.
+    pub fn is_alive(&self, row: u32, col: u32) -> bool {
+        self.get_cell(row, col) == Ok(Cell::Alive)
+    }
+
+    pub fn set_cell(&mut self, row: u32, col: u32, state: Cell) -> Result<(), Error> {
+        let idx = self.checked_index(row, col)?;
+        self.record_cells(&[idx]);
+        self.cells[idx] = state;
+        Ok(())
+    }
+
+    pub fn set_alive(&mut self, row: u32, col: u32) -> Result<(), Error> {
+        self.set_cell(row, col, Cell::Alive)
+    }
+
+    pub fn set_dead(&mut self, row: u32, col: u32) -> Result<(), Error> {
+        self.set_cell(row, col, Cell::Dead)
+    }
+
+    /// Sets every `[row, col]` pair of a flat coordinate array alive in one call.
+    ///
+    /// Nothing is changed if any coordinate is outside the grid.
+    #[wasm_bindgen(js_name = set_cells)]
+    pub fn set_cells_flat(&mut self, coords: &[u32]) -> Result<(), Error> {
+        let indices = self.pair_indices(coords)?;
+        self.record_cells(&indices);
+        for idx in indices {
+            self.cells[idx] = Cell::Alive;
+        }
+        Ok(())
+    }
+
+    /// Flips every `[row, col]` pair of a flat coordinate array.
+    ///
+    /// Applying the same delta twice is a no-op, so a delta doubles as its own undo.
+    /// Nothing is changed if any coordinate is outside the grid.
+    pub fn apply_delta(&mut self, toggles: &[u32]) -> Result<(), Error> {
+        let indices = self.pair_indices(toggles)?;
+        self.record_cells(&indices);
+        for idx in indices {
+            self.cells[idx].toggle();
+        }
+        Ok(())
+    }
+
+    /// Flat `[row, col, ...]` list of the cells flipped by the most recent `tick()`,
+    /// suitable for `apply_delta()` on another copy of the previous generation.
+    pub fn changed_cells(&self) -> Vec<u32> {
+        self.changed.clone()
+    }
+
+    /// Flat `[row, col, ...]` list of live cells, cheaper than scanning `cells()`
+    /// for sparse states.
+    pub fn live_cells(&self) -> Vec<u32> {
+        self.iter_live_cells()
+            .flat_map(|(row, col)| [row, col])
+            .collect()
+    }
+
+    /// Flips a cell between alive and dead, e.g. from a canvas click handler.
+    pub fn toggle_cell(&mut self, row: u32, col: u32) -> Result<(), Error> {
+        let idx = self.checked_index(row, col)?;
+        self.record_cells(&[idx]);
+        self.cells[idx].toggle();
+        Ok(())
+    }
+
+    /// Marks or unmarks a wall: a cell that is permanently dead and ignored by the rules.
+    pub fn set_wall(&mut self, row: u32, col: u32, wall: bool) -> Result<(), Error> {
+        self.set_flag(row, col, WALL, wall)
+    }
+
+    pub fn is_wall(&self, row: u32, col: u32) -> bool {
+        self.checked_index(row, col)
+            .is_ok_and(|idx| self.flags[idx] & WALL != 0)
+    }
+
+    /// Removes every wall.
+    pub fn clear_walls(&mut self) {
+        self.clear_flag(WALL);
+    }
+
+    /// Marks or unmarks an anchor: a cell pinned alive that the rules never kill.
+    pub fn set_anchor(&mut self, row: u32, col: u32, anchor: bool) -> Result<(), Error> {
+        self.set_flag(row, col, ANCHOR, anchor)
+    }
+
+    pub fn is_anchor(&self, row: u32, col: u32) -> bool {
+        self.checked_index(row, col)
+            .is_ok_and(|idx| self.flags[idx] & ANCHOR != 0)
+    }
+
+    /// Removes every anchor, leaving the cells alive.
+    pub fn clear_anchors(&mut self) {
+        self.clear_flag(ANCHOR);
+    }
+
+    /// Pointer to one byte of flag bits per cell (`WALL` = 1, `ANCHOR` = 2), laid out like `cells()`.
+    pub fn flags(&self) -> *const u8 {
+        self.flags.as_ptr()
+    }
+
+    /// Labels a cell with an arbitrary byte for renderers. Tags are ignored by `tick()`;
+    /// 0 means untagged.
+    pub fn set_tag(&mut self, row: u32, col: u32, tag: u8) -> Result<(), Error> {
+        let idx = self.checked_index(row, col)?;
+        if self.tags.is_empty() {
+            self.record_edit();
+            self.tags.resize(self.cells.len(), 0);
+        } else {
+            self.record_cells(&[idx]);
+        }
+        self.tags[idx] = tag;
+        Ok(())
+    }
+
+    /// The tag of a cell, or 0 if it has none or lies outside the grid.
+    pub fn tag(&self, row: u32, col: u32) -> u8 {
+        match self.checked_index(row, col) {
+            Ok(idx) => self.tags.get(idx).copied().unwrap_or(0),
+            Err(_) => 0,
+        }
+    }
+
+    /// Whether the tag layer has been allocated; `tags()` is only valid when it has.
+    pub fn has_tags(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    /// Drops every tag and frees the tag layer.
+    pub fn clear_tags(&mut self) {
+        self.record_edit();
+        self.tags = Vec::new();
+    }
+
+    /// Pointer to one tag byte per cell, laid out like `cells()`.
+    pub fn tags(&self) -> *const u8 {
+        self.tags.as_ptr()
+    }
+
+    /// Pointer to the current cells. Ticks write into a second buffer and then swap
+    /// the two, so the pointer alternates between them: read it again after every
+    /// tick instead of keeping it. Edits, and resizes within `reserve()`d room,
+    /// leave it in place.
     pub fn cells(&self) -> *const Cell {
         self.cells.as_ptr()
     }
 
     pub fn tick(&mut self) {
-        let mut next = self.cells.clone();
-
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let idx = self.get_index(row, col);
-                let cell = self.cells[idx];
-                let live_neighbors = self.get_live_neighbor_count(row, col);
-
-                let next_cell = match (cell, live_neighbors) {
-                    (Cell::Alive, x) if x < 2 => Cell::Dead, // Rule 1: Alive cell with less than 2 neighbors dies by underpopulation
-                    (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive, // Rule 2: Alive cell with 2 or 3 neighbors lives
-                    (Cell::Alive, x) if x > 3 => Cell::Dead, // Rule 3: Alive cell with over 3 neighbors dies by overpopulation
-                    (Cell::Dead, 3) => Cell::Alive, // Rule 4: Dead cell with 3 neighbors lives by reproduction
-                    (state, _) => state,            // otherwise: stay the same
-                };
-
-                next[idx] = next_cell;
-            }
+        self.protect_history();
+        self.advance_schedule();
+        match self.engine {
+            Engine::Life(rule) => self.tick_life(rule),
+            Engine::Isotropic(_) => self.tick_isotropic(),
+            Engine::Langton(_) => self.tick_ants(),
+            Engine::Elementary(ca) => self.tick_elementary(ca),
+            Engine::Custom(ref rule) => self.tick_custom(rule.clone()),
+            _ => self.tick_states(),
         }
+        self.apply_noise();
+        self.save_activity();
+        self.record_frame();
+    }
 
-        // Replace current cells with buffer
-        self.cells = next;
+    /// Advances `n` generations in a single call, for fast-forwarding without a
+    /// round trip through JS per generation. `changed_cells()` afterwards covers
+    /// only the last of them.
+    pub fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    /// Independent copy with the same cells, size and settings, for branching a run.
+    pub fn duplicate(&self) -> Universe {
+        self.clone()
     }
 
-    pub fn generate_glider(&mut self) {
-        let cells = [(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)];
-        self.set_cells(&cells);
+    /// Remembers the current cells as the state `reset()` returns to.
+    ///
+    /// Constructors call this automatically once the universe is set up.
+    pub fn save_initial(&mut self) {
+        self.initial = self.snapshot();
+    }
+
+    /// Restores the saved initial state, including the random generator, so
+    /// a run replays exactly. The cell buffer is reused when the size is unchanged.
+    pub fn reset(&mut self) {
+        self.record_edit();
+        let initial = self.initial.clone();
+        self.restore(&initial);
+        self.rng = initial.rng;
+    }
+
+    /// Pre-allocates room for a `max_width` x `max_height` grid so later resizes up
+    /// to that size do not grow WASM memory or move the `cells()` buffer.
+    pub fn reserve(&mut self, max_width: u32, max_height: u32) -> Result<(), Error> {
+        check_size(max_width, max_height)?;
+        let total = (max_width * max_height) as usize;
+        self.cells
+            .try_reserve(total.saturating_sub(self.cells.len()))
+            .and_then(|_| {
+                self.flags
+                    .try_reserve(total.saturating_sub(self.flags.len()))
+            })
+            .and_then(|_| self.back.try_reserve(total.saturating_sub(self.back.len())))
+            .map_err(|_| Error::OutOfMemory { cells: total })
+    }
+
+    /// Number of cells the buffer can hold without reallocating.
+    pub fn capacity(&self) -> u32 {
+        self.cells.capacity() as u32
+    }
+
+    /// Releases any reserved room beyond the current grid size.
+    pub fn shrink_to_fit(&mut self) {
+        self.cells.shrink_to_fit();
+        self.back = Vec::new();
+        self.flags.shrink_to_fit();
+        self.tags.shrink_to_fit();
+        self.states.shrink_to_fit();
+        self.back_states = Vec::new();
+    }
+
+    /// Kills every cell while keeping the size, settings and cell buffer.
+    pub fn clear(&mut self) {
+        self.record_edit();
+        for cell in self.cells.iter_mut() {
+            *cell = Cell::Dead;
+        }
+    }
+
+    /// Kills every cell in a `height` x `width` rectangle, e.g. for an eraser tool.
+    /// Parts outside the grid are ignored.
+    pub fn clear_region(&mut self, row: u32, col: u32, height: u32, width: u32) {
+        self.record_edit();
+        self.fill_region(row, col, height, width, Cell::Dead);
+    }
+
+    /// Refills every cell, making each one alive with probability `density`.
+    pub fn randomize(&mut self, density: f64) {
+        self.record_edit();
+        self.fill_random(density);
+    }
+}
+
+/// Indexes cells by `(row, col)`, panicking outside the grid.
+impl Index<(u32, u32)> for Universe {
+    type Output = Cell;
+
+    fn index(&self, (row, col): (u32, u32)) -> &Cell {
+        assert!(
+            row < self.height && col < self.width,
+            "cell ({}, {}) is outside the universe",
+            row,
+            col
+        );
+        &self.cells[self.get_index(row, col)]
+    }
+}
+
+impl IndexMut<(u32, u32)> for Universe {
+    fn index_mut(&mut self, (row, col): (u32, u32)) -> &mut Cell {
+        assert!(
+            row < self.height && col < self.width,
+            "cell ({}, {}) is outside the universe",
+            row,
+            col
+        );
+        self.unrecorded_change();
+        let idx = self.get_index(row, col);
+        &mut self.cells[idx]
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Universe::new()
+    }
+}
+
+fn check_size(width: u32, height: u32) -> Result<(), Error> {
+    match width.checked_mul(height) {
+        Some(n) if n > 0 => Ok(()),
+        _ => Err(Error::InvalidSize { width, height }),
     }
 }
 
@@ -154,7 +956,7 @@ impl fmt::Display for Universe {
                 let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
                 write!(f, "{}", symbol)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
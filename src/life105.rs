@@ -0,0 +1,133 @@
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::Pattern;
+use crate::{Error, SparseUniverse, Universe};
+
+// Decoded Life 1.05 file before its points are normalized
+struct Life105 {
+    // `#N` or `#R survive/birth`, converted to `B/S` notation
+    rule: Option<String>,
+    // `(row, col)` points, which may be negative
+    points: Vec<(i32, i32)>,
+}
+
+// Decodes Life 1.05: `#` header lines, then blocks of `.`/`*` rows, each starting
+// with a `#P x y` line giving its top-left corner
+fn decode(text: &str) -> Result<Life105, Error> {
+    let mut rule = None;
+    let mut points = Vec::new();
+    let (mut origin_row, mut origin_col) = (0i32, 0i32);
+    let mut row = 0i32;
+
+    for (line_no, line) in text.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("#P") {
+            let mut fields = rest.split_whitespace().map(|field| field.parse::<i32>());
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => {
+                    origin_row = y;
+                    origin_col = x;
+                    row = 0;
+                }
+                _ => return Err(parse_error(line_no, "expected \"#P x y\" block offset")),
+            }
+        } else if line.starts_with("#N") {
+            rule = Some("B3/S23".to_string());
+        } else if let Some(rest) = line.strip_prefix("#R") {
+            rule = Some(parse_rule(rest.trim(), line_no)?);
+        } else if line.starts_with('#') {
+            // #Life, #D and unknown lines carry no cells
+        } else {
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '.' => {}
+                    '*' => {
+                        let point = origin_row.checked_add(row).zip(
+                            i32::try_from(col)
+                                .ok()
+                                .and_then(|col| origin_col.checked_add(col)),
+                        );
+                        let point = point.ok_or_else(|| {
+                            parse_error(line_no, "cell lies beyond the coordinate range")
+                        })?;
+                        points.push(point);
+                    }
+                    other => {
+                        return Err(parse_error(
+                            line_no,
+                            &format!("unexpected character '{}'", other),
+                        ))
+                    }
+                }
+            }
+            row += 1;
+        }
+    }
+
+    Ok(Life105 { rule, points })
+}
+
+/// Decodes Life 1.05 (`#P` blocks of `.`/`*` rows) into a pattern whose bounding
+/// box starts at the origin.
+pub fn parse(text: &str) -> Result<Pattern, Error> {
+    let decoded = decode(text)?;
+    let mut pattern = Pattern::from_points(&decoded.points)?;
+    pattern.rule = decoded.rule;
+    Ok(pattern)
+}
+
+// Converts a `#R` rule written as `survive/birth` into `B../S..` notation
+fn parse_rule(rule: &str, line_no: usize) -> Result<String, Error> {
+    let mut parts = rule.splitn(2, '/');
+    let survive = parts.next().unwrap_or("");
+    let birth = parts
+        .next()
+        .ok_or_else(|| parse_error(line_no, "expected \"#R survive/birth\" rule"))?;
+    if !survive
+        .chars()
+        .chain(birth.chars())
+        .all(|c| ('0'..='8').contains(&c))
+    {
+        return Err(parse_error(line_no, &format!("invalid rule '{}'", rule)));
+    }
+    Ok(format!("B{}/S{}", birth, survive))
+}
+
+fn parse_error(line: usize, message: &str) -> Error {
+    Error::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Creates a `width` x `height` universe with a Life 1.05 pattern centered in it.
+    pub fn from_life105(text: &str, width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::from_pattern(&parse(text)?, width, height)
+    }
+
+    /// Stamps a Life 1.05 pattern with the top-left corner of its bounding box at
+    /// `row`, `col`, wrapping past the edges.
+    pub fn load_life105(&mut self, text: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.load_pattern(&parse(text)?, row, col)
+    }
+}
+
+#[wasm_bindgen]
+impl SparseUniverse {
+    /// Decodes Life 1.05 keeping its block offsets as they are, negative ones included.
+    pub fn from_life105(text: &str) -> Result<SparseUniverse, Error> {
+        let mut universe = SparseUniverse::new();
+        for (row, col) in decode(text)?.points {
+            universe.set_alive(row, col);
+        }
+        Ok(universe)
+    }
+}
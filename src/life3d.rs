@@ -0,0 +1,190 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+use crate::rng::{Generator, Rng};
+use crate::{check_size, Error, RngKind};
+
+/// Experimental three-dimensional Life: a stack of `depth` layers of `width` x
+/// `height` cells, each counting the 26 cells of the surrounding 3x3x3 cube as
+/// neighbors. All three axes wrap around.
+///
+/// Rules use Carter Bays' notation `"4555"`: a live cell survives with 4 to 5 live
+/// neighbors and a dead one is born with 5 to 5, which is the default.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Life3d {
+    width: u32,
+    height: u32,
+    depth: u32,
+    // One byte per cell, 0 or 1, layer by layer and then row-major
+    cells: Vec<u8>,
+    survival: (u8, u8),
+    birth: (u8, u8),
+    generation: u32,
+}
+
+impl Life3d {
+    fn index(&self, layer: u32, row: u32, col: u32) -> usize {
+        ((layer * self.height + row) * self.width + col) as usize
+    }
+
+    fn live_neighbors(&self, layer: u32, row: u32, col: u32) -> u8 {
+        let mut count = 0;
+        for dl in [self.depth - 1, 0, 1] {
+            for dr in [self.height - 1, 0, 1] {
+                for dc in [self.width - 1, 0, 1] {
+                    if dl == 0 && dr == 0 && dc == 0 {
+                        continue;
+                    }
+                    let idx = self.index(
+                        (layer + dl) % self.depth,
+                        (row + dr) % self.height,
+                        (col + dc) % self.width,
+                    );
+                    count += self.cells[idx];
+                }
+            }
+        }
+        count
+    }
+}
+
+impl fmt::Display for Life3d {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (survival, birth) = (self.survival, self.birth);
+        if [survival.0, survival.1, birth.0, birth.1]
+            .iter()
+            .all(|&n| n < 10)
+        {
+            write!(f, "{}{}{}{}", survival.0, survival.1, birth.0, birth.1)
+        } else {
+            write!(f, "{},{},{},{}", survival.0, survival.1, birth.0, birth.1)
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Life3d {
+    /// Creates an empty volume of `depth` layers of `width` x `height` cells.
+    pub fn new(width: u32, height: u32, depth: u32) -> Result<Life3d, Error> {
+        check_size(width, height)?;
+        let cells = (width * height).checked_mul(depth).filter(|&n| n > 0);
+        let cells = cells.ok_or(Error::InvalidSize { width, height })?;
+        Ok(Life3d {
+            width,
+            height,
+            depth,
+            cells: vec![0; cells as usize],
+            survival: (4, 5),
+            birth: (5, 5),
+            generation: 0,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Sets the rule in Bays' notation: survival minimum and maximum, then birth
+    /// minimum and maximum, as four digits like `"5766"` or, for counts above 9,
+    /// four comma-separated numbers like `"4,5,5,5"`.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), Error> {
+        let invalid = || Error::InvalidRule(rule.to_string());
+        let rule = rule.trim();
+        let counts: Option<Vec<u8>> = if rule.contains(',') {
+            rule.split(',').map(|n| n.trim().parse().ok()).collect()
+        } else {
+            rule.chars()
+                .map(|c| c.to_digit(10).map(|n| n as u8))
+                .collect()
+        };
+        match counts.as_deref() {
+            Some(&[s0, s1, b0, b1]) if [s0, s1, b0, b1].iter().all(|&n| n <= 26) => {
+                self.survival = (s0, s1);
+                self.birth = (b0, b1);
+                Ok(())
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn rule(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn is_alive(&self, layer: u32, row: u32, col: u32) -> bool {
+        layer < self.depth
+            && row < self.height
+            && col < self.width
+            && self.cells[self.index(layer, row, col)] != 0
+    }
+
+    /// Sets one cell. Coordinates outside the volume are ignored.
+    pub fn set_cell(&mut self, layer: u32, row: u32, col: u32, alive: bool) {
+        if layer < self.depth && row < self.height && col < self.width {
+            let idx = self.index(layer, row, col);
+            self.cells[idx] = alive as u8;
+        }
+    }
+
+    /// Fills the volume with cells alive with probability `density`, reproducible
+    /// for the same `seed`.
+    pub fn randomize(&mut self, seed: u64, density: f64) {
+        let mut rng = Generator::new(RngKind::XorShift, seed);
+        for cell in self.cells.iter_mut() {
+            *cell = (rng.next_f64() < density) as u8;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| *cell = 0);
+    }
+
+    pub fn population(&self) -> u32 {
+        self.cells.iter().map(|&cell| cell as u32).sum()
+    }
+
+    /// The cells of one layer as `width` x `height` bytes in row-major order, 1 for
+    /// alive, ready to render like a 2D universe. Empty for a layer past the bottom.
+    pub fn slice(&self, layer: u32) -> Vec<u8> {
+        if layer >= self.depth {
+            return Vec::new();
+        }
+        let start = self.index(layer, 0, 0);
+        self.cells[start..start + (self.width * self.height) as usize].to_vec()
+    }
+
+    /// Pointer to all cells, one byte each, layer after layer.
+    pub fn cells(&self) -> *const u8 {
+        self.cells.as_ptr()
+    }
+
+    pub fn tick(&mut self) {
+        let within = |n: u8, (low, high): (u8, u8)| low <= n && n <= high;
+        let mut next = Vec::with_capacity(self.cells.len());
+        for layer in 0..self.depth {
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    let alive = self.cells[self.index(layer, row, col)] != 0;
+                    let n = self.live_neighbors(layer, row, col);
+                    let range = if alive { self.survival } else { self.birth };
+                    next.push(within(n, range) as u8);
+                }
+            }
+        }
+        self.cells = next;
+        self.generation += 1;
+    }
+}
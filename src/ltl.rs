@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::{Boundary, Error, Universe};
+
+// Shape of a Larger than Life neighborhood
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Shape {
+    // Every cell within `radius` rows and columns (NM)
+    Square,
+    // Every cell within Manhattan distance `radius` (NN)
+    Diamond,
+}
+
+/// A Larger than Life rule: birth and survival depend on the number of live cells
+/// within `radius`, falling in an inclusive range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LtlRule {
+    radius: u32,
+    // Total number of states; cells that fail to survive decay like Generations
+    states: u8,
+    include_center: bool,
+    survival: (u32, u32),
+    birth: (u32, u32),
+    shape: Shape,
+}
+
+impl LtlRule {
+    /// Reads Golly's notation, e.g. `"R5,C0,M1,S34..58,B34..45,NM"` (Bugs).
+    pub fn parse(rule: &str) -> Result<LtlRule, Error> {
+        let invalid = || Error::InvalidRule(rule.to_string());
+        let number = |digits: &str| digits.parse::<u32>().map_err(|_| invalid());
+        let range = |bounds: &str| match bounds.split_once("..") {
+            Some((low, high)) => Ok((number(low)?, number(high)?)),
+            None => number(bounds).map(|n| (n, n)),
+        };
+
+        let (mut radius, mut survival, mut birth) = (None, None, None);
+        let mut parsed = LtlRule {
+            radius: 1,
+            states: 2,
+            include_center: false,
+            survival: (0, 0),
+            birth: (0, 0),
+            shape: Shape::Square,
+        };
+        for token in rule.trim().split(',').map(str::trim) {
+            let (key, value) = token.split_at(token.char_indices().nth(1).map_or(0, |(i, _)| i));
+            match key.to_ascii_uppercase().as_str() {
+                "R" => radius = Some(number(value)?),
+                "C" => {
+                    parsed.states = match number(value)? {
+                        0..=2 => 2,
+                        states if states <= 255 => states as u8,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "M" => parsed.include_center = number(value)? != 0,
+                "S" => survival = Some(range(value)?),
+                "B" => birth = Some(range(value)?),
+                "N" => {
+                    parsed.shape = match value.to_ascii_uppercase().as_str() {
+                        "M" => Shape::Square,
+                        "N" => Shape::Diamond,
+                        _ => return Err(invalid()),
+                    }
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        parsed.radius = radius
+            .filter(|r| (1..=500).contains(r))
+            .ok_or_else(invalid)?;
+        parsed.survival = survival.ok_or_else(invalid)?;
+        parsed.birth = birth.ok_or_else(invalid)?;
+        Ok(parsed)
+    }
+
+    pub fn state_count(self) -> u32 {
+        self.states as u32
+    }
+
+    pub fn next_state(self, state: u8, live: u32) -> u8 {
+        let within = |(low, high): (u32, u32)| low <= live && live <= high;
+        match state {
+            0 if within(self.birth) => 1,
+            0 => 0,
+            1 if within(self.survival) => 1,
+            _ if state + 1 < self.states => state + 1,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for LtlRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "R{},C{},M{},S{}..{},B{}..{},N{}",
+            self.radius,
+            if self.states == 2 { 0 } else { self.states },
+            self.include_center as u8,
+            self.survival.0,
+            self.survival.1,
+            self.birth.0,
+            self.birth.1,
+            if self.shape == Shape::Square {
+                'M'
+            } else {
+                'N'
+            }
+        )
+    }
+}
+
+impl Universe {
+    // Live cells (state 1) in every cell's neighborhood under `rule`, in row-major order.
+    // Square neighborhoods are summed in constant time per cell from a summed-area table
+    // over the grid padded by the radius.
+    pub(crate) fn ltl_counts(&self, rule: &LtlRule) -> Vec<u32> {
+        let (width, height, r) = (self.width as i64, self.height as i64, rule.radius as i64);
+        let live = |row: i64, col: i64| -> u32 {
+            let (row, col) = match self.boundary {
+                Boundary::Wrap => (row.rem_euclid(height), col.rem_euclid(width)),
+                Boundary::Fixed if row < 0 || col < 0 || row >= height || col >= width => return 0,
+                Boundary::Fixed => (row, col),
+            };
+            (self.states[(row * width + col) as usize] == 1) as u32
+        };
+
+        let mut counts = Vec::with_capacity(self.states.len());
+        match rule.shape {
+            Shape::Square => {
+                // sums[i][j] holds the live cells above and left of padded cell (i, j)
+                let (padded_w, padded_h) = ((width + 2 * r) as usize, (height + 2 * r) as usize);
+                let stride = padded_w + 1;
+                let mut sums = vec![0u32; (padded_h + 1) * stride];
+                for i in 0..padded_h {
+                    let mut row_sum = 0;
+                    for j in 0..padded_w {
+                        row_sum += live(i as i64 - r, j as i64 - r);
+                        sums[(i + 1) * stride + j + 1] = sums[i * stride + j + 1] + row_sum;
+                    }
+                }
+
+                let side = 2 * r as usize + 1;
+                for row in 0..height as usize {
+                    for col in 0..width as usize {
+                        let (top, left) = (row, col);
+                        let (bottom, right) = (row + side, col + side);
+                        counts.push(
+                            sums[bottom * stride + right] + sums[top * stride + left]
+                                - sums[top * stride + right]
+                                - sums[bottom * stride + left],
+                        );
+                    }
+                }
+            }
+            Shape::Diamond => {
+                for row in 0..height {
+                    for col in 0..width {
+                        let mut count = 0;
+                        for dr in -r..=r {
+                            let reach = r - dr.abs();
+                            for dc in -reach..=reach {
+                                count += live(row + dr, col + dc);
+                            }
+                        }
+                        counts.push(count);
+                    }
+                }
+            }
+        }
+
+        if !rule.include_center {
+            for (count, &state) in counts.iter_mut().zip(self.states.iter()) {
+                *count -= (state == 1) as u32;
+            }
+        }
+        counts
+    }
+}
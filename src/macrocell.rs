@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::Pattern;
+use crate::{check_size, Cell, Error, Universe};
+
+// Leaves are 8x8 blocks, i.e. quadtree level 3
+const LEAF_LEVEL: u32 = 3;
+const LEAF_SIZE: u32 = 1 << LEAF_LEVEL;
+
+// One numbered line of a macrocell file
+enum Node {
+    // Live `(row, col)` offsets inside an 8x8 block
+    Leaf(Vec<(u32, u32)>),
+    // Level and node numbers of the nw, ne, sw and se quadrants; 0 is empty
+    Branch(u32, [usize; 4]),
+}
+
+/// Decodes Golly's two-state macrocell format: a `[M2]` header, `#` comment and
+/// rule lines, then quadtree nodes numbered from 1 with the root last.
+///
+/// Patterns whose bounding box would not fit in a universe are rejected before
+/// any cells are expanded.
+pub fn parse(text: &str) -> Result<Pattern, Error> {
+    let mut nodes = Vec::new();
+    let mut rule = None;
+
+    for (line_no, line) in text.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("[M2]") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("#R") {
+            rule = Some(rest.trim().to_string());
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let node = if line.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_branch(line, line_no, &nodes)?
+        } else {
+            parse_leaf(line, line_no)?
+        };
+        nodes.push(node);
+    }
+
+    // Shared subtrees can describe far more cells than the file holds, so check
+    // the size of the whole pattern first
+    let mut points = Vec::new();
+    if let Some(&Some((top, left, bottom, right))) = extents(&nodes).last() {
+        let span = |low: u64, high: u64| u32::try_from(high - low + 1).ok();
+        match (span(left, right), span(top, bottom)) {
+            (Some(width), Some(height)) if check_size(width, height).is_ok() => {}
+            (width, height) => {
+                return Err(Error::PatternTooLarge {
+                    width: width.unwrap_or(u32::MAX),
+                    height: height.unwrap_or(u32::MAX),
+                })
+            }
+        }
+        flatten(&nodes, nodes.len(), 0, 0, &mut points);
+    }
+    let min_row = points.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = points.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let cells = points
+        .iter()
+        .map(|&(row, col)| ((row - min_row) as u32, (col - min_col) as u32))
+        .collect();
+
+    let mut pattern = Pattern::from_cells(cells)?;
+    pattern.rule = rule;
+    Ok(pattern)
+}
+
+fn parse_leaf(line: &str, line_no: usize) -> Result<Node, Error> {
+    let mut cells = Vec::new();
+    let (mut row, mut col) = (0u32, 0u32);
+    for c in line.chars() {
+        match c {
+            '.' | '*' if row >= LEAF_SIZE || col >= LEAF_SIZE => {
+                return Err(parse_error(line_no, "leaf node is larger than 8x8"));
+            }
+            '.' => col += 1,
+            '*' => {
+                cells.push((row, col));
+                col += 1;
+            }
+            '$' => {
+                row += 1;
+                col = 0;
+            }
+            other => {
+                return Err(parse_error(
+                    line_no,
+                    &format!("unexpected character '{}' in leaf node", other),
+                ))
+            }
+        }
+    }
+    Ok(Node::Leaf(cells))
+}
+
+fn parse_branch(line: &str, line_no: usize, nodes: &[Node]) -> Result<Node, Error> {
+    let fields = line
+        .split_whitespace()
+        .map(|field| field.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| parse_error(line_no, &format!("invalid node '{}'", line)))?;
+    if fields.len() != 5 {
+        return Err(parse_error(line_no, "expected \"level nw ne sw se\""));
+    }
+
+    let level = fields[0] as u32;
+    if level <= LEAF_LEVEL {
+        return Err(parse_error(
+            line_no,
+            "multi-state macrocell files are not supported",
+        ));
+    }
+    if level > 63 {
+        return Err(parse_error(line_no, "node level is too large"));
+    }
+
+    let mut children = [0; 4];
+    for (child, &index) in children.iter_mut().zip(&fields[1..]) {
+        let child_level = match index {
+            0 => level - 1,
+            _ => match nodes.get(index - 1) {
+                Some(Node::Leaf(_)) => LEAF_LEVEL,
+                Some(Node::Branch(child_level, _)) => *child_level,
+                None => {
+                    return Err(parse_error(
+                        line_no,
+                        &format!("node {} is not defined yet", index),
+                    ))
+                }
+            },
+        };
+        if child_level != level - 1 {
+            return Err(parse_error(
+                line_no,
+                &format!("node {} has the wrong level", index),
+            ));
+        }
+        *child = index;
+    }
+    Ok(Node::Branch(level, children))
+}
+
+// Top, left, bottom and right of a node's live cells, relative to its corner
+type Extent = (u64, u64, u64, u64);
+
+fn grow(extent: Option<Extent>, (top, left, bottom, right): Extent) -> Option<Extent> {
+    Some(match extent {
+        Some(extent) => (
+            extent.0.min(top),
+            extent.1.min(left),
+            extent.2.max(bottom),
+            extent.3.max(right),
+        ),
+        None => (top, left, bottom, right),
+    })
+}
+
+// The extent of every node, `None` for empty ones, without expanding any; each
+// node only refers to nodes before it
+fn extents(nodes: &[Node]) -> Vec<Option<Extent>> {
+    let mut extents: Vec<Option<Extent>> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let extent = match node {
+            Node::Leaf(cells) => cells.iter().fold(None, |extent, &(row, col)| {
+                let (row, col) = (row as u64, col as u64);
+                grow(extent, (row, col, row, col))
+            }),
+            Node::Branch(level, children) => {
+                let half = 1u64 << (level - 1);
+                let offsets = [(0, 0), (0, half), (half, 0), (half, half)];
+                children
+                    .iter()
+                    .zip(offsets.iter())
+                    .filter(|&(&child, _)| child != 0)
+                    .filter_map(|(&child, &(dr, dc))| {
+                        let (top, left, bottom, right) = extents[child - 1]?;
+                        Some((top + dr, left + dc, bottom + dr, right + dc))
+                    })
+                    .fold(None, grow)
+            }
+        };
+        extents.push(extent);
+    }
+    extents
+}
+
+// Collects the live cells of node `index` with its top-left corner at `top`, `left`
+fn flatten(nodes: &[Node], index: usize, top: u64, left: u64, points: &mut Vec<(u64, u64)>) {
+    match &nodes[index - 1] {
+        Node::Leaf(cells) => {
+            points.extend(
+                cells
+                    .iter()
+                    .map(|&(row, col)| (top + row as u64, left + col as u64)),
+            );
+        }
+        Node::Branch(level, children) => {
+            let half = 1u64 << (level - 1);
+            let offsets = [(0, 0), (0, half), (half, 0), (half, half)];
+            for (&child, &(dr, dc)) in children.iter().zip(offsets.iter()) {
+                if child != 0 {
+                    flatten(nodes, child, top + dr, left + dc, points);
+                }
+            }
+        }
+    }
+}
+
+fn parse_error(line: usize, message: &str) -> Error {
+    Error::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+// Writes quadtree nodes bottom-up, sharing identical subtrees
+struct Encoder<'a> {
+    universe: &'a Universe,
+    lines: Vec<String>,
+    seen: HashMap<String, usize>,
+}
+
+impl<'a> Encoder<'a> {
+    // Returns the node number for the square at `top`, `left`, or 0 if it is empty
+    fn node(&mut self, level: u32, top: u32, left: u32) -> usize {
+        if top >= self.universe.height || left >= self.universe.width {
+            return 0;
+        }
+
+        let line = if level == LEAF_LEVEL {
+            match self.leaf(top, left) {
+                Some(line) => line,
+                None => return 0,
+            }
+        } else {
+            let half = 1 << (level - 1);
+            let children = [
+                self.node(level - 1, top, left),
+                self.node(level - 1, top, left + half),
+                self.node(level - 1, top + half, left),
+                self.node(level - 1, top + half, left + half),
+            ];
+            if children == [0; 4] {
+                return 0;
+            }
+            format!(
+                "{} {} {} {} {}",
+                level, children[0], children[1], children[2], children[3]
+            )
+        };
+
+        if let Some(&index) = self.seen.get(&line) {
+            return index;
+        }
+        self.lines.push(line.clone());
+        self.seen.insert(line, self.lines.len());
+        self.lines.len()
+    }
+
+    // An 8x8 leaf with trailing dead cells and rows left out, or `None` if it is empty
+    fn leaf(&self, top: u32, left: u32) -> Option<String> {
+        let universe = self.universe;
+        let mut rows = Vec::new();
+        for row in top..(top + LEAF_SIZE).min(universe.height) {
+            let mut line: String = (left..(left + LEAF_SIZE).min(universe.width))
+                .map(|col| match universe.cells[universe.get_index(row, col)] {
+                    Cell::Alive => '*',
+                    Cell::Dead => '.',
+                })
+                .collect();
+            line.truncate(line.trim_end_matches('.').len());
+            rows.push(line);
+        }
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        if rows.is_empty() {
+            return None;
+        }
+        Some(rows.iter().map(|row| format!("{}$", row)).collect())
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Creates a `width` x `height` universe with a macrocell pattern centered in it.
+    pub fn from_macrocell(text: &str, width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::from_pattern(&parse(text)?, width, height)
+    }
+
+    /// Stamps a macrocell pattern with the top-left corner of its bounding box at
+    /// `row`, `col`, wrapping past the edges.
+    pub fn load_macrocell(&mut self, text: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.load_pattern(&parse(text)?, row, col)
+    }
+
+    /// The whole grid in Golly's macrocell format, with identical 8x8 blocks and
+    /// larger squares written once.
+    pub fn to_macrocell(&self) -> String {
+        let mut level = LEAF_LEVEL;
+        while (1u64 << level) < self.width.max(self.height) as u64 {
+            level += 1;
+        }
+
+        let mut encoder = Encoder {
+            universe: self,
+            lines: Vec::new(),
+            seen: HashMap::new(),
+        };
+        encoder.node(level, 0, 0);
+
+        let mut text = format!("[M2] (wasm-game-of-life)\n#R {}\n", self.rule());
+        for line in encoder.lines {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+}
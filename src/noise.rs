@@ -0,0 +1,90 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Rng, Universe};
+
+// Number of octaves summed for fractal noise; each one doubles the frequency
+const OCTAVES: u32 = 3;
+
+/// Seeded 2D value noise: random values on an integer lattice, smoothly interpolated.
+pub struct ValueNoise {
+    seed: u64,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u64) -> ValueNoise {
+        ValueNoise { seed }
+    }
+
+    // Pseudo-random value in [0, 1) for a lattice point
+    fn lattice(&self, x: i64, y: i64) -> f64 {
+        let mut z = self.seed ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z ^= (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Noise in `[0, 1)` at a point, continuous in both coordinates.
+    pub fn sample(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (ix, iy) = (x0 as i64, y0 as i64);
+        let tx = smoothstep(x - x0);
+        let ty = smoothstep(y - y0);
+
+        let top = lerp(self.lattice(ix, iy), self.lattice(ix + 1, iy), tx);
+        let bottom = lerp(self.lattice(ix, iy + 1), self.lattice(ix + 1, iy + 1), tx);
+        lerp(top, bottom, ty)
+    }
+
+    /// Sum of octaves of `sample`, normalized back into `[0, 1)`.
+    pub fn fractal(&self, x: f64, y: f64) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut norm = 0.0;
+        let mut frequency = 1.0;
+
+        for _ in 0..OCTAVES {
+            total += amplitude * self.sample(x * frequency, y * frequency);
+            norm += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        total / norm
+    }
+}
+
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Refills the grid from fractal value noise, giving clumpy organic regions.
+    ///
+    /// `scale` is the approximate feature size in cells and a cell is alive where
+    /// the noise is above `threshold` (noise values lie in `[0, 1)`).
+    pub fn randomize_noise(&mut self, scale: f64, threshold: f64) {
+        self.record_edit();
+        let noise = ValueNoise::new(self.rng.next_u64());
+        let scale = scale.max(f64::MIN_POSITIVE);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let value = noise.fractal(col as f64 / scale, row as f64 / scale);
+                let idx = self.get_index(row, col);
+                self.cells[idx] = if value > threshold {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+            }
+        }
+    }
+}
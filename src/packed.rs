@@ -0,0 +1,249 @@
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::rng::{Generator, Rng};
+use crate::rule::LifeRule;
+use crate::{check_size, Cell, Error, RngKind, Universe};
+
+/// Wrapping universe storing one bit per cell, 64 cells to a word, and ticking a
+/// whole word of cells at once. Uses an eighth of the memory of `Universe`, which
+/// suits very large grids, but only runs birth/survival rules in the Moore
+/// neighborhood.
+///
+/// Each row starts a new word; column `c` is bit `c % 64` of word `c / 64`, and the
+/// bits past the last column stay clear.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct PackedUniverse {
+    width: u32,
+    height: u32,
+    words_per_row: usize,
+    words: Vec<u64>,
+    rule: LifeRule,
+    generation: u32,
+}
+
+// Adds one bit per column to a four-bit count held as bit planes, lowest first
+fn add(planes: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+    for plane in planes.iter_mut() {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+impl PackedUniverse {
+    fn row(&self, row: u32) -> &[u64] {
+        let start = row as usize * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    // Mask of the columns in use in a row's last word
+    fn last_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => !0,
+            used => (1 << used) - 1,
+        }
+    }
+
+    fn get_bit(row: &[u64], col: u32) -> u64 {
+        row[col as usize / 64] >> (col % 64) & 1
+    }
+
+    // Each column gets the bit of the column to its left, wrapping around
+    fn west_neighbors(&self, row: &[u64]) -> Vec<u64> {
+        let mut shifted: Vec<u64> = (0..row.len())
+            .map(|i| row[i] << 1 | if i > 0 { row[i - 1] >> 63 } else { 0 })
+            .collect();
+        shifted[0] |= Self::get_bit(row, self.width - 1);
+        *shifted.last_mut().unwrap() &= self.last_mask();
+        shifted
+    }
+
+    // Each column gets the bit of the column to its right, wrapping around
+    fn east_neighbors(&self, row: &[u64]) -> Vec<u64> {
+        let mut shifted: Vec<u64> = (0..row.len())
+            .map(|i| row[i] >> 1 | row.get(i + 1).map_or(0, |&next| next << 63))
+            .collect();
+        let last = self.width - 1;
+        shifted[last as usize / 64] |= Self::get_bit(row, 0) << (last % 64);
+        shifted
+    }
+}
+
+#[wasm_bindgen]
+impl PackedUniverse {
+    /// Creates an empty `width` x `height` universe running Conway's Life.
+    pub fn new(width: u32, height: u32) -> Result<PackedUniverse, Error> {
+        check_size(width, height)?;
+        let words_per_row = width.div_ceil(64) as usize;
+        Ok(PackedUniverse {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height as usize],
+            rule: LifeRule::CONWAY,
+            generation: 0,
+        })
+    }
+
+    /// Packs the cells of `universe`, keeping its rule if it is a birth/survival
+    /// rule and running Conway's Life otherwise.
+    pub fn from_universe(universe: &Universe) -> PackedUniverse {
+        let mut packed = PackedUniverse::new(universe.width, universe.height)
+            .expect("universes have a valid size");
+        if let Engine::Life(rule) = universe.engine {
+            packed.rule = rule;
+        }
+        for (idx, &cell) in universe.cells.iter().enumerate() {
+            let (row, col) = (idx as u32 / universe.width, idx as u32 % universe.width);
+            packed.set_cell(row, col, cell);
+        }
+        packed
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Switches to a birth/survival rule such as `"B36/S23"`.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), Error> {
+        self.rule = LifeRule::parse(rule)?;
+        Ok(())
+    }
+
+    pub fn rule(&self) -> String {
+        self.rule.to_string()
+    }
+
+    pub fn is_alive(&self, row: u32, col: u32) -> bool {
+        row < self.height && col < self.width && Self::get_bit(self.row(row), col) != 0
+    }
+
+    /// Sets one cell. Coordinates outside the grid are ignored.
+    pub fn set_cell(&mut self, row: u32, col: u32, cell: Cell) {
+        if row < self.height && col < self.width {
+            let idx = row as usize * self.words_per_row + col as usize / 64;
+            let bit = 1 << (col % 64);
+            match cell {
+                Cell::Alive => self.words[idx] |= bit,
+                Cell::Dead => self.words[idx] &= !bit,
+            }
+        }
+    }
+
+    /// Fills the grid with cells alive with probability `density`, reproducible for
+    /// the same `seed`.
+    pub fn randomize(&mut self, seed: u64, density: f64) {
+        let mut rng = Generator::new(RngKind::XorShift, seed);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let alive = rng.next_f64() < density;
+                self.set_cell(row, col, if alive { Cell::Alive } else { Cell::Dead });
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    pub fn population(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Words in each row of `words()`: the width divided by 64, rounded up.
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    /// Pointer to the packed cells, `words_per_row()` 64-bit words per row. Read it
+    /// again after each tick, which replaces the buffer.
+    pub fn words(&self) -> *const u64 {
+        self.words.as_ptr()
+    }
+
+    /// The cells unpacked to one byte each, 1 for alive, laid out like `Universe::cells()`.
+    pub fn to_cells(&self) -> Vec<u8> {
+        let mut cells = Vec::with_capacity((self.width * self.height) as usize);
+        for row in 0..self.height {
+            let words = self.row(row);
+            cells.extend((0..self.width).map(|col| Self::get_bit(words, col) as u8));
+        }
+        cells
+    }
+
+    pub fn tick(&mut self) {
+        let lookup = self.rule.lookup();
+        // Sideways neighbors of every row, shared by the rows above and below
+        let west: Vec<Vec<u64>> = (0..self.height)
+            .map(|row| self.west_neighbors(self.row(row)))
+            .collect();
+        let east: Vec<Vec<u64>> = (0..self.height)
+            .map(|row| self.east_neighbors(self.row(row)))
+            .collect();
+
+        let mut next = Vec::with_capacity(self.words.len());
+        for row in 0..self.height {
+            let above = (row + self.height - 1) % self.height;
+            let below = (row + 1) % self.height;
+            let neighbors = [
+                (
+                    self.row(above),
+                    &west[above as usize],
+                    &east[above as usize],
+                ),
+                (
+                    self.row(below),
+                    &west[below as usize],
+                    &east[below as usize],
+                ),
+            ];
+            let cells = self.row(row);
+
+            for i in 0..self.words_per_row {
+                let mut planes = [0; 4];
+                for (vertical, west, east) in neighbors.iter() {
+                    add(&mut planes, vertical[i]);
+                    add(&mut planes, west[i]);
+                    add(&mut planes, east[i]);
+                }
+                add(&mut planes, west[row as usize][i]);
+                add(&mut planes, east[row as usize][i]);
+
+                // Columns whose count matches each `n` the rule turns alive
+                let alive = cells[i];
+                let mut word = 0;
+                let transitions = lookup[Cell::Alive as usize]
+                    .iter()
+                    .zip(&lookup[Cell::Dead as usize]);
+                for (n, (&survival, &birth)) in transitions.enumerate().take(9) {
+                    let matches = planes.iter().enumerate().fold(!0, |mask, (bit, &plane)| {
+                        mask & if n >> bit & 1 != 0 { plane } else { !plane }
+                    });
+                    if survival == Cell::Alive {
+                        word |= matches & alive;
+                    }
+                    if birth == Cell::Alive {
+                        word |= matches & !alive;
+                    }
+                }
+                if i + 1 == self.words_per_row {
+                    word &= self.last_mask();
+                }
+                next.push(word);
+            }
+        }
+        self.words = next;
+        self.generation += 1;
+    }
+}
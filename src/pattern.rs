@@ -0,0 +1,188 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{Error, Universe};
+
+/// One of the eight ways to rotate or mirror a pattern.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    Identity = 0,
+    RotateCw = 1,
+    Rotate180 = 2,
+    RotateCcw = 3,
+    FlipHorizontal = 4,
+    FlipVertical = 5,
+    /// Mirror across the main diagonal.
+    Transpose = 6,
+    /// Mirror across the anti-diagonal.
+    AntiTranspose = 7,
+}
+
+impl Orientation {
+    pub(crate) const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::RotateCw,
+        Orientation::Rotate180,
+        Orientation::RotateCcw,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+        Orientation::Transpose,
+        Orientation::AntiTranspose,
+    ];
+}
+
+/// A finite pattern: its bounding box and the offsets of its live cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pattern {
+    pub width: u32,
+    pub height: u32,
+    /// `(row, col)` offsets from the top-left corner of the bounding box.
+    pub cells: Vec<(u32, u32)>,
+    /// Rulestring from the source file, if it declared one.
+    pub rule: Option<String>,
+}
+
+impl Pattern {
+    /// Builds a pattern whose bounding box exactly fits `cells`, failing if a side
+    /// would be longer than `u32::MAX`.
+    pub fn from_cells(cells: Vec<(u32, u32)>) -> Result<Pattern, Error> {
+        let size = |max: Option<u32>| max.map_or(Some(0), |max| max.checked_add(1));
+        let height = size(cells.iter().map(|&(row, _)| row).max());
+        let width = size(cells.iter().map(|&(_, col)| col).max());
+        match (width, height) {
+            (Some(width), Some(height)) => Ok(Pattern {
+                width,
+                height,
+                cells,
+                rule: None,
+            }),
+            (width, height) => Err(Error::PatternTooLarge {
+                width: width.unwrap_or(u32::MAX),
+                height: height.unwrap_or(u32::MAX),
+            }),
+        }
+    }
+
+    /// Builds a pattern from signed `(row, col)` points, shifted so that its bounding
+    /// box starts at the origin. Duplicates are dropped.
+    pub fn from_points(points: &[(i32, i32)]) -> Result<Pattern, Error> {
+        let min_row = points.iter().map(|&(row, _)| row).min().unwrap_or(0) as i64;
+        let min_col = points.iter().map(|&(_, col)| col).min().unwrap_or(0) as i64;
+
+        let mut cells: Vec<_> = points
+            .iter()
+            .map(|&(row, col)| ((row as i64 - min_row) as u32, (col as i64 - min_col) as u32))
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        Pattern::from_cells(cells)
+    }
+
+    /// A copy rotated or mirrored within its bounding box.
+    pub fn oriented(&self, orientation: Orientation) -> Pattern {
+        let (width, height) = match orientation {
+            Orientation::RotateCw
+            | Orientation::RotateCcw
+            | Orientation::Transpose
+            | Orientation::AntiTranspose => (self.height, self.width),
+            _ => (self.width, self.height),
+        };
+        let (last_row, last_col) = (self.height.saturating_sub(1), self.width.saturating_sub(1));
+        let cells = self
+            .cells
+            .iter()
+            .map(|&(row, col)| match orientation {
+                Orientation::Identity => (row, col),
+                Orientation::RotateCw => (col, last_row - row),
+                Orientation::Rotate180 => (last_row - row, last_col - col),
+                Orientation::RotateCcw => (last_col - col, row),
+                Orientation::FlipHorizontal => (row, last_col - col),
+                Orientation::FlipVertical => (last_row - row, col),
+                Orientation::Transpose => (col, row),
+                Orientation::AntiTranspose => (last_col - col, last_row - row),
+            })
+            .collect();
+        Pattern {
+            width,
+            height,
+            cells,
+            rule: self.rule.clone(),
+        }
+    }
+}
+
+impl Universe {
+    // Creates a `width` x `height` universe with `pattern` centered in it, running
+    // the pattern's rule when it is one `set_rule()` understands
+    pub(crate) fn from_pattern(
+        pattern: &Pattern,
+        width: u32,
+        height: u32,
+    ) -> Result<Universe, Error> {
+        if pattern.width > width || pattern.height > height {
+            return Err(Error::PatternTooLarge {
+                width: pattern.width,
+                height: pattern.height,
+            });
+        }
+
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        universe.stamp(
+            (height - pattern.height) / 2,
+            (width - pattern.width) / 2,
+            &pattern.cells,
+        );
+        if let Some(rule) = pattern.rule.as_deref() {
+            // Unsupported rules fall back to Conway's Life
+            let _ = universe.set_rule(rule);
+        }
+        universe.save_initial();
+        Ok(universe)
+    }
+
+    // Stamps `pattern` as an edit, refusing patterns bigger than the grid
+    pub(crate) fn load_pattern(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+    ) -> Result<(), Error> {
+        if pattern.width > self.width || pattern.height > self.height {
+            return Err(Error::PatternTooLarge {
+                width: pattern.width,
+                height: pattern.height,
+            });
+        }
+
+        self.record_edit();
+        self.stamp(row, col, &pattern.cells);
+        Ok(())
+    }
+}
+
+// Decodes pattern text in any supported format, recognised by its header
+pub(crate) fn parse_any(text: &str) -> Result<Pattern, Error> {
+    let first = text.lines().map(str::trim).find(|line| !line.is_empty());
+    match first {
+        Some(line) if line.starts_with("[M2]") => crate::macrocell::parse(text),
+        Some(line) if line.starts_with("#Life 1.06") => crate::life106::parse(text),
+        Some(line) if line.starts_with("#Life 1.05") => crate::life105::parse(text),
+        Some(line) if line.starts_with('!') || line.chars().all(|c| c == '.' || c == 'O') => {
+            crate::plaintext::parse(text)
+        }
+        _ => crate::rle::parse(text),
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Stamps a pattern in RLE, `.cells`, Life 1.05/1.06 or macrocell format, telling
+    /// them apart by their first line, with its top-left corner at `row`, `col`.
+    pub fn load_any(&mut self, text: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.load_pattern(&parse_any(text)?, row, col)
+    }
+}
@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+
+use crate::{rle, Error, Orientation, Pattern, Universe};
+
+// Relative live-cell coordinates of a glider heading down and to the right
+const GLIDER: [(u32, u32); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+// Gosper's glider gun, firing a glider down and to the right every 30 generations
+#[rustfmt::skip]
+const GOSPER_GLIDER_GUN: [(u32, u32); 36] = [
+    (0, 24),
+    (1, 22), (1, 24),
+    (2, 12), (2, 13), (2, 20), (2, 21), (2, 34), (2, 35),
+    (3, 11), (3, 15), (3, 20), (3, 21), (3, 34), (3, 35),
+    (4, 0), (4, 1), (4, 10), (4, 16), (4, 20), (4, 21),
+    (5, 0), (5, 1), (5, 10), (5, 14), (5, 16), (5, 17), (5, 22), (5, 24),
+    (6, 10), (6, 16), (6, 24),
+    (7, 11), (7, 15),
+    (8, 12), (8, 13),
+];
+
+// Period-3 oscillator with four-fold symmetry
+#[rustfmt::skip]
+const PULSAR: [(u32, u32); 48] = [
+    (0, 2), (0, 3), (0, 4), (0, 8), (0, 9), (0, 10),
+    (2, 0), (2, 5), (2, 7), (2, 12),
+    (3, 0), (3, 5), (3, 7), (3, 12),
+    (4, 0), (4, 5), (4, 7), (4, 12),
+    (5, 2), (5, 3), (5, 4), (5, 8), (5, 9), (5, 10),
+    (7, 2), (7, 3), (7, 4), (7, 8), (7, 9), (7, 10),
+    (8, 0), (8, 5), (8, 7), (8, 12),
+    (9, 0), (9, 5), (9, 7), (9, 12),
+    (10, 0), (10, 5), (10, 7), (10, 12),
+    (12, 2), (12, 3), (12, 4), (12, 8), (12, 9), (12, 10),
+];
+
+// Period-15 oscillator
+#[rustfmt::skip]
+const PENTADECATHLON: [(u32, u32); 12] = [
+    (0, 2), (0, 7),
+    (1, 0), (1, 1), (1, 3), (1, 4), (1, 5), (1, 6), (1, 8), (1, 9),
+    (2, 2), (2, 7),
+];
+
+// Spaceships, all travelling right at c/2
+#[rustfmt::skip]
+const LWSS: [(u32, u32); 9] = [
+    (0, 0), (0, 3),
+    (1, 4),
+    (2, 0), (2, 4),
+    (3, 1), (3, 2), (3, 3), (3, 4),
+];
+
+#[rustfmt::skip]
+const MWSS: [(u32, u32); 11] = [
+    (0, 2),
+    (1, 0), (1, 4),
+    (2, 5),
+    (3, 0), (3, 5),
+    (4, 1), (4, 2), (4, 3), (4, 4), (4, 5),
+];
+
+#[rustfmt::skip]
+const HWSS: [(u32, u32); 13] = [
+    (0, 2), (0, 3),
+    (1, 0), (1, 5),
+    (2, 6),
+    (3, 0), (3, 6),
+    (4, 1), (4, 2), (4, 3), (4, 4), (4, 5), (4, 6),
+];
+
+// Named patterns available to `insert_pattern()` and the builder
+const BUILTIN: &[(&str, &[(u32, u32)])] = &[
+    ("glider", &GLIDER),
+    ("block", &[(0, 0), (0, 1), (1, 0), (1, 1)]),
+    ("beehive", &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)]),
+    (
+        "loaf",
+        &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 3), (3, 2)],
+    ),
+    ("boat", &[(0, 0), (0, 1), (1, 0), (1, 2), (2, 1)]),
+    ("tub", &[(0, 1), (1, 0), (1, 2), (2, 1)]),
+    ("blinker", &[(0, 0), (0, 1), (0, 2)]),
+    ("toad", &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)]),
+    (
+        "beacon",
+        &[
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (1, 1),
+            (2, 2),
+            (2, 3),
+            (3, 2),
+            (3, 3),
+        ],
+    ),
+    ("pulsar", &PULSAR),
+    ("pentadecathlon", &PENTADECATHLON),
+    ("gosper_glider_gun", &GOSPER_GLIDER_GUN),
+    ("lwss", &LWSS),
+    ("mwss", &MWSS),
+    ("hwss", &HWSS),
+    // Methuselahs: small seeds that take hundreds of generations to settle
+    ("r_pentomino", &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)]),
+    (
+        "acorn",
+        &[(0, 1), (1, 3), (2, 0), (2, 1), (2, 4), (2, 5), (2, 6)],
+    ),
+    (
+        "diehard",
+        &[(0, 6), (1, 0), (1, 1), (2, 1), (2, 5), (2, 6), (2, 7)],
+    ),
+];
+
+/// Heading for `insert_spaceship()`.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Right = 0,
+    Left = 1,
+    Down = 2,
+    Up = 3,
+}
+
+impl Direction {
+    // How to turn a right-travelling ship to head this way
+    fn orientation(self) -> Orientation {
+        match self {
+            Direction::Right => Orientation::Identity,
+            Direction::Left => Orientation::FlipHorizontal,
+            Direction::Down => Orientation::RotateCw,
+            Direction::Up => Orientation::RotateCcw,
+        }
+    }
+}
+
+thread_local! {
+    // Patterns added with `register_pattern()`, in registration order
+    static REGISTERED: RefCell<Vec<(String, Pattern)>> = const { RefCell::new(Vec::new()) };
+}
+
+// Looks up a pattern by name; registered patterns shadow built-ins
+pub(crate) fn lookup(name: &str) -> Option<Pattern> {
+    let registered = REGISTERED.with(|registered| {
+        registered
+            .borrow()
+            .iter()
+            .find(|(registered, _)| registered == name)
+            .map(|(_, pattern)| pattern.clone())
+    });
+    let builtin = || {
+        BUILTIN
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|&(_, cells)| {
+                Pattern::from_cells(cells.to_vec()).expect("built-in patterns are small")
+            })
+    };
+    registered.or_else(builtin).or_else(|| lexicon(name))
+}
+
+// Embedded lexicon entries, when that feature is enabled
+#[cfg(feature = "lexicon")]
+fn lexicon(name: &str) -> Option<Pattern> {
+    crate::lexicon::lookup(name)
+}
+
+#[cfg(not(feature = "lexicon"))]
+fn lexicon(_name: &str) -> Option<Pattern> {
+    None
+}
+
+/// Adds an RLE pattern under `name` for `insert_pattern()` and the builder,
+/// replacing any earlier pattern of that name, built-ins included.
+#[wasm_bindgen]
+pub fn register_pattern(name: &str, rle: &str) -> Result<(), Error> {
+    let pattern = rle::parse(rle)?;
+    REGISTERED.with(|registered| {
+        let mut registered = registered.borrow_mut();
+        match registered.iter_mut().find(|(existing, _)| existing == name) {
+            Some(entry) => entry.1 = pattern,
+            None => registered.push((name.to_string(), pattern)),
+        }
+    });
+    Ok(())
+}
+
+/// Removes a pattern added with `register_pattern()`. Returns `false` if there was none.
+#[wasm_bindgen]
+pub fn unregister_pattern(name: &str) -> bool {
+    REGISTERED.with(|registered| {
+        let mut registered = registered.borrow_mut();
+        let before = registered.len();
+        registered.retain(|(existing, _)| existing != name);
+        registered.len() != before
+    })
+}
+
+/// Names accepted by `insert_pattern()`: the built-ins, any embedded lexicon
+/// patterns, then registered patterns.
+#[wasm_bindgen]
+pub fn pattern_names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN.iter().map(|(name, _)| name.to_string()).collect();
+    #[cfg(feature = "lexicon")]
+    names.extend(crate::lexicon::list_patterns());
+    REGISTERED.with(|registered| {
+        for (name, _) in registered.borrow().iter() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    });
+    names
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Stamps the built-in or registered pattern `name` with its top-left corner at `row`, `col`,
+    /// wrapping past the edges. See `pattern_names()` for what is available.
+    pub fn insert_pattern(&mut self, name: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.insert_pattern_oriented(name, row, col, Orientation::Identity)
+    }
+
+    /// Like `insert_pattern()`, but rotates or mirrors the pattern first. The top-left
+    /// corner of the reoriented bounding box lands at `row`, `col`.
+    pub fn insert_pattern_oriented(
+        &mut self,
+        name: &str,
+        row: u32,
+        col: u32,
+        orientation: Orientation,
+    ) -> Result<(), Error> {
+        let pattern = lookup(name)
+            .ok_or_else(|| Error::UnknownPattern(name.to_string()))?
+            .oriented(orientation);
+        self.record_edit();
+        self.stamp(row, col, &pattern.cells);
+        Ok(())
+    }
+
+    /// Stamps the spaceship `name` ("lwss", "mwss" or "hwss") with its top-left corner
+    /// at `row`, `col`, turned to travel in `direction`.
+    pub fn insert_spaceship(
+        &mut self,
+        name: &str,
+        row: u32,
+        col: u32,
+        direction: Direction,
+    ) -> Result<(), Error> {
+        self.insert_pattern_oriented(name, row, col, direction.orientation())
+    }
+
+    pub fn generate_glider(&mut self) {
+        self.record_edit();
+        self.stamp(1, 1, &GLIDER);
+    }
+
+    /// Places a Gosper glider gun (36x9) with its top-left corner at `row`, `col`.
+    pub fn generate_glider_gun(&mut self, row: u32, col: u32) {
+        self.record_edit();
+        self.stamp(row, col, &GOSPER_GLIDER_GUN);
+    }
+}
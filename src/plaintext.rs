@@ -0,0 +1,82 @@
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::Pattern;
+use crate::{Error, Universe};
+
+/// Decodes the plaintext `.cells` format: `!` comment lines, then one line per row
+/// with `.` for dead and `O` for live cells. Empty lines are empty rows.
+pub fn parse(text: &str) -> Result<Pattern, Error> {
+    let mut cells = Vec::new();
+    let (mut width, mut height) = (0u32, 0u32);
+    let mut row = 0u32;
+
+    for (line_no, line) in text.lines().enumerate() {
+        if line.starts_with('!') {
+            continue;
+        }
+
+        let line = line.trim_end();
+        for (col, c) in line.chars().enumerate() {
+            match c {
+                '.' => {}
+                'O' => cells.push((row, col as u32)),
+                other => {
+                    return Err(Error::Parse {
+                        line: line_no + 1,
+                        message: format!("unexpected character '{}'", other),
+                    })
+                }
+            }
+        }
+        // Trailing empty rows carry no information
+        if !line.is_empty() {
+            width = width.max(line.chars().count() as u32);
+            height = row + 1;
+        }
+        row += 1;
+    }
+
+    Ok(Pattern {
+        width,
+        height,
+        cells,
+        rule: None,
+    })
+}
+
+/// Encodes a pattern as `.cells` text with an optional `!Name:` comment.
+pub fn encode(pattern: &Pattern, name: Option<&str>) -> String {
+    let mut rows = vec![vec![b'.'; pattern.width as usize]; pattern.height as usize];
+    for &(row, col) in pattern.cells.iter() {
+        rows[row as usize][col as usize] = b'O';
+    }
+
+    let mut text = String::new();
+    if let Some(name) = name {
+        text.push_str(&format!("!Name: {}\n", name));
+    }
+    for row in rows {
+        text.push_str(&String::from_utf8(row).unwrap());
+        text.push('\n');
+    }
+    text
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Creates a `width` x `height` universe with a `.cells` pattern centered in it.
+    pub fn from_plaintext(text: &str, width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::from_pattern(&parse(text)?, width, height)
+    }
+
+    /// Stamps a `.cells` pattern with its top-left corner at `row`, `col`, wrapping
+    /// past the edges.
+    pub fn load_plaintext(&mut self, text: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.load_pattern(&parse(text)?, row, col)
+    }
+
+    /// The whole grid in `.cells` format.
+    pub fn to_plaintext(&self) -> String {
+        encode(&self.region_pattern(0, 0, self.height, self.width), None)
+    }
+}
@@ -0,0 +1,287 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Error, Universe};
+
+const MAGIC: &[u8; 3] = b"GLR";
+const VERSION: u8 = 1;
+
+// A run being captured by `start_recording()`
+#[derive(Clone)]
+pub(crate) struct Recording {
+    // `to_bytes()` of the universe when recording started
+    initial: Vec<u8>,
+    // Cells as of the last stored frame, to diff the next generation against
+    last: Vec<Cell>,
+    // Ascending indices of the cells that flipped, one list per generation
+    frames: Vec<Vec<u32>>,
+    active: bool,
+}
+
+/// A run saved with `export_replay()`, for watching again with `play_replay()`.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Replay {
+    initial: Vec<u8>,
+    frames: Vec<Vec<u32>>,
+}
+
+// Position within a replay being watched
+#[derive(Clone)]
+pub(crate) struct Playback {
+    frames: Vec<Vec<u32>>,
+    position: usize,
+}
+
+// LEB128: seven bits per byte, low bits first, high bit set on all but the last
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u32, Error> {
+    let mut value = 0u32;
+    for shift in (0..32).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or_else(|| invalid("truncated frame"))?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid("varint too long"))
+}
+
+fn read_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, Error> {
+    let word = bytes
+        .get(*pos..*pos + 4)
+        .ok_or_else(|| invalid("truncated header"))?;
+    *pos += 4;
+    Ok(u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidData(message.to_string())
+}
+
+#[wasm_bindgen]
+impl Replay {
+    /// Reads a replay written by `export_replay()`, checking every frame against
+    /// the size of the starting state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Replay, Error> {
+        if bytes.len() < MAGIC.len() + 1 || &bytes[..3] != MAGIC {
+            return Err(invalid("missing replay header"));
+        }
+        if bytes[3] != VERSION {
+            return Err(invalid(&format!("unsupported replay version {}", bytes[3])));
+        }
+
+        let mut pos = 4;
+        let initial_len = read_u32(bytes, &mut pos)? as usize;
+        let initial = bytes
+            .get(pos..pos + initial_len)
+            .ok_or_else(|| invalid("truncated starting state"))?
+            .to_vec();
+        pos += initial_len;
+        let cells = Universe::from_bytes(&initial)?.cells.len();
+
+        let count = read_u32(bytes, &mut pos)?;
+        let mut frames = Vec::new();
+        for _ in 0..count {
+            let flipped = read_varint(bytes, &mut pos)? as usize;
+            if flipped > cells {
+                return Err(invalid("frame flips more cells than exist"));
+            }
+            let mut frame = Vec::with_capacity(flipped);
+            let mut idx = 0u32;
+            for _ in 0..flipped {
+                idx = idx
+                    .checked_add(read_varint(bytes, &mut pos)?)
+                    .filter(|&idx| (idx as usize) < cells)
+                    .ok_or_else(|| invalid("cell index out of range"))?;
+                frame.push(idx);
+            }
+            frames.push(frame);
+        }
+        if pos != bytes.len() {
+            return Err(invalid("trailing bytes after the last frame"));
+        }
+
+        Ok(Replay { initial, frames })
+    }
+
+    /// Number of generations in the replay.
+    pub fn frame_count(&self) -> u32 {
+        self.frames.len() as u32
+    }
+}
+
+impl Universe {
+    // Called by `tick()` to store the generation it just produced
+    pub(crate) fn record_frame(&mut self) {
+        let recording = match self.recording.as_mut() {
+            Some(recording) if recording.active => recording,
+            _ => return,
+        };
+        if recording.last.len() != self.cells.len() {
+            // Deltas can't describe a resize
+            recording.active = false;
+            return;
+        }
+
+        let flipped = self
+            .cells
+            .iter()
+            .zip(recording.last.iter())
+            .enumerate()
+            .filter(|(_, (now, before))| now != before)
+            .map(|(idx, _)| idx as u32)
+            .collect();
+        recording.frames.push(flipped);
+        recording.last.clone_from(&self.cells);
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Starts capturing a replay from the current state, discarding any earlier
+    /// recording. Every `tick()` then stores the cells that changed since the last
+    /// frame, so edits made between ticks are folded into the next frame.
+    /// Resizing the universe stops the recording.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Recording {
+            initial: self.to_bytes(),
+            last: self.cells.clone(),
+            frames: Vec::new(),
+            active: true,
+        });
+    }
+
+    /// Stops adding frames. What was recorded so far can still be exported.
+    pub fn stop_recording(&mut self) {
+        if let Some(recording) = self.recording.as_mut() {
+            recording.active = false;
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+            .as_ref()
+            .is_some_and(|recording| recording.active)
+    }
+
+    /// Number of generations stored by the current or last recording.
+    pub fn recorded_frames(&self) -> u32 {
+        self.recording
+            .as_ref()
+            .map_or(0, |recording| recording.frames.len() as u32)
+    }
+
+    /// The recording as a compact binary replay: a "GLR" magic and version byte,
+    /// the starting state in the `to_bytes()` format behind its length, then the
+    /// frame count and each frame as a LEB128 varint count of flipped cells
+    /// followed by the gaps between their ascending indices.
+    pub fn export_replay(&self) -> Result<Vec<u8>, Error> {
+        let recording = self.recording.as_ref().ok_or(Error::NoRecording)?;
+
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 9 + recording.initial.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(recording.initial.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&recording.initial);
+        bytes.extend_from_slice(&(recording.frames.len() as u32).to_le_bytes());
+        for frame in recording.frames.iter() {
+            write_varint(&mut bytes, frame.len() as u32);
+            let mut previous = 0;
+            for &idx in frame.iter() {
+                write_varint(&mut bytes, idx - previous);
+                previous = idx;
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Loads the starting state of `replay`, replacing the grid as an undoable
+    /// edit, and rewinds playback to its first frame. Editing or resizing the
+    /// universe during playback makes later frames meaningless.
+    pub fn play_replay(&mut self, replay: &Replay) -> Result<(), Error> {
+        let start = Universe::from_bytes(&replay.initial)?;
+        self.record_edit();
+        self.width = start.width;
+        self.height = start.height;
+        self.cells = start.cells;
+        self.flags = start.flags;
+        self.tags = start.tags;
+        self.states = start.states;
+        self.boundary = start.boundary;
+        self.engine = start.engine;
+        self.neighborhood = start.neighborhood;
+        self.count_self = start.count_self;
+        self.selection = None;
+        self.changed.clear();
+        self.playback = Some(Playback {
+            frames: replay.frames.clone(),
+            position: 0,
+        });
+        Ok(())
+    }
+
+    /// Advances playback by one stored generation. Returns `false` at the end of
+    /// the replay or when no replay is playing.
+    pub fn step_replay(&mut self) -> bool {
+        let frame = match self.playback.as_mut() {
+            Some(playback) if playback.position < playback.frames.len() => {
+                playback.position += 1;
+                playback.frames[playback.position - 1].clone()
+            }
+            _ => return false,
+        };
+        self.apply_frame(&frame)
+    }
+
+    /// Steps playback back one generation by undoing the last applied frame.
+    /// Returns `false` at the start of the replay or when no replay is playing.
+    pub fn step_replay_back(&mut self) -> bool {
+        let frame = match self.playback.as_mut() {
+            Some(playback) if playback.position > 0 => {
+                playback.position -= 1;
+                playback.frames[playback.position].clone()
+            }
+            _ => return false,
+        };
+        self.apply_frame(&frame)
+    }
+
+    /// Index of the next frame `step_replay()` will apply, or 0 when not playing.
+    pub fn replay_position(&self) -> u32 {
+        self.playback
+            .as_ref()
+            .map_or(0, |playback| playback.position as u32)
+    }
+
+    pub fn stop_replay(&mut self) {
+        self.playback = None;
+    }
+}
+
+impl Universe {
+    // Flips the cells of one frame; frames are their own inverse
+    fn apply_frame(&mut self, frame: &[u32]) -> bool {
+        if frame.iter().any(|&idx| idx as usize >= self.cells.len()) {
+            // The universe was resized under the replay
+            self.playback = None;
+            return false;
+        }
+
+        self.unrecorded_change();
+        self.changed.clear();
+        for &idx in frame.iter() {
+            self.cells[idx as usize].toggle();
+            self.changed.push(idx / self.width);
+            self.changed.push(idx % self.width);
+        }
+        true
+    }
+}
@@ -0,0 +1,241 @@
+use wasm_bindgen::prelude::*;
+
+use crate::pattern::Pattern;
+use crate::{check_size, Cell, Error, Universe};
+
+/// Decodes Run Length Encoded text: `#` comment lines, an optional
+/// `x = .., y = .., rule = ..` header and `b`/`o`/`$` runs ending in `!`.
+///
+/// Any tag letter other than `b` counts as a live cell.
+pub fn parse(text: &str) -> Result<Pattern, Error> {
+    let mut pattern = Pattern::default();
+    let mut cells = Vec::new();
+    let (mut row, mut col) = (0u32, 0u32);
+    let mut seen_header = false;
+    // Width and height from the header, which live cells must stay within
+    let mut bounds = None;
+    let mut finished = false;
+
+    for (line_no, line) in text.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line.trim();
+        if finished {
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !seen_header && line.starts_with('x') {
+            parse_header(line, line_no, &mut pattern)?;
+            seen_header = true;
+            bounds = Some((pattern.width, pattern.height));
+            continue;
+        }
+        seen_header = true;
+
+        let mut count: Option<u32> = None;
+        for c in line.chars() {
+            match c {
+                '0'..='9' => {
+                    let digit = c.to_digit(10).unwrap();
+                    count = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit));
+                    if count.is_none() {
+                        return Err(parse_error(line_no, "run count is too large"));
+                    }
+                }
+                'b' | '.' => {
+                    col = col.saturating_add(count.take().unwrap_or(1));
+                }
+                '$' => {
+                    row = row.saturating_add(count.take().unwrap_or(1));
+                    col = 0;
+                }
+                '!' => {
+                    finished = true;
+                    break;
+                }
+                c if c.is_ascii_alphabetic() => {
+                    let run = count.take().unwrap_or(1);
+                    let end = col
+                        .checked_add(run)
+                        .filter(|&end| fits(bounds, row, end))
+                        .ok_or_else(|| parse_error(line_no, "cells run past the pattern size"))?;
+                    cells.extend((col..end).map(|col| (row, col)));
+                    col = end;
+                }
+                c if c.is_whitespace() => {}
+                other => {
+                    return Err(parse_error(
+                        line_no,
+                        &format!("unexpected character '{}' in pattern data", other),
+                    ))
+                }
+            }
+        }
+        if count.is_some() {
+            return Err(parse_error(line_no, "run count at end of line has no tag"));
+        }
+    }
+
+    let fitted = Pattern::from_cells(cells)?;
+    pattern.width = pattern.width.max(fitted.width);
+    pattern.height = pattern.height.max(fitted.height);
+    pattern.cells = fitted.cells;
+    Ok(pattern)
+}
+
+// Longest line `encode()` writes, as recommended by the format
+const LINE_LIMIT: usize = 70;
+
+/// Encodes a pattern as RLE with an `x = .., y = ..` header, wrapping lines at
+/// 70 characters. Uses the pattern's rule, defaulting to `B3/S23`.
+pub fn encode(pattern: &Pattern) -> String {
+    let mut cells = pattern.cells.clone();
+    cells.sort_unstable();
+    cells.dedup();
+
+    let mut writer = RunWriter::default();
+    let (mut row, mut col) = (0, 0);
+    let mut i = 0;
+    while i < cells.len() {
+        let (r, c) = cells[i];
+        if r > row {
+            writer.push(r - row, '$');
+            row = r;
+            col = 0;
+        }
+        if c > col {
+            writer.push(c - col, 'b');
+        }
+
+        let mut run = 1;
+        while i + run < cells.len() && cells[i + run] == (r, c + run as u32) {
+            run += 1;
+        }
+        writer.push(run as u32, 'o');
+        col = c + run as u32;
+        i += run;
+    }
+    writer.push(1, '!');
+
+    format!(
+        "x = {}, y = {}, rule = {}\n{}\n",
+        pattern.width,
+        pattern.height,
+        pattern.rule.as_deref().unwrap_or("B3/S23"),
+        writer.lines.join("\n")
+    )
+}
+
+// Collects `<count><tag>` runs into lines no longer than `LINE_LIMIT`
+#[derive(Default)]
+struct RunWriter {
+    lines: Vec<String>,
+}
+
+impl RunWriter {
+    fn push(&mut self, count: u32, tag: char) {
+        let run = if count == 1 {
+            tag.to_string()
+        } else {
+            format!("{}{}", count, tag)
+        };
+        match self.lines.last_mut() {
+            Some(line) if line.len() + run.len() <= LINE_LIMIT => line.push_str(&run),
+            _ => self.lines.push(run),
+        }
+    }
+}
+
+// Whether live cells in `row` up to column `end` fit the header's size or, for
+// a pattern without one, any universe
+fn fits(bounds: Option<(u32, u32)>, row: u32, end: u32) -> bool {
+    match bounds {
+        Some((width, height)) => end <= width && row < height,
+        None => row
+            .checked_add(1)
+            .is_some_and(|height| check_size(end, height).is_ok()),
+    }
+}
+
+fn parse_header(line: &str, line_no: usize, pattern: &mut Pattern) -> Result<(), Error> {
+    for field in line.split(',') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts
+            .next()
+            .ok_or_else(|| parse_error(line_no, &format!("header field '{}' has no value", key)))?
+            .trim();
+
+        match key {
+            "x" | "y" => {
+                let n = value.parse::<u32>().map_err(|_| {
+                    parse_error(line_no, &format!("invalid {} value '{}'", key, value))
+                })?;
+                if key == "x" {
+                    pattern.width = n;
+                } else {
+                    pattern.height = n;
+                }
+            }
+            "rule" => pattern.rule = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn parse_error(line: usize, message: &str) -> Error {
+    Error::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+impl Universe {
+    // Live cells of a clipped rectangle, relative to its top-left corner, under the current rule
+    pub(crate) fn region_pattern(&self, row: u32, col: u32, height: u32, width: u32) -> Pattern {
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        let mut cells = Vec::new();
+        for r in rows.clone() {
+            for c in cols.clone() {
+                if self.cells[self.get_index(r, c)] == Cell::Alive {
+                    cells.push((r - rows.start, c - cols.start));
+                }
+            }
+        }
+        Pattern {
+            width: cols.len() as u32,
+            height: rows.len() as u32,
+            cells,
+            rule: Some(self.rule()).filter(|rule| !rule.is_empty()),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Creates a `width` x `height` universe with an RLE pattern centered in it.
+    pub fn from_rle(rle: &str, width: u32, height: u32) -> Result<Universe, Error> {
+        Universe::from_pattern(&parse(rle)?, width, height)
+    }
+
+    /// The whole grid as RLE, e.g. for pasting into Golly or LifeViewer.
+    pub fn to_rle(&self) -> String {
+        self.region_to_rle(0, 0, self.height, self.width)
+    }
+
+    /// A `height` x `width` rectangle of the grid as RLE, clipped to the grid.
+    pub fn region_to_rle(&self, row: u32, col: u32, height: u32, width: u32) -> String {
+        encode(&self.region_pattern(row, col, height, width))
+    }
+
+    /// Stamps an RLE pattern with its top-left corner at `row`, `col`, wrapping past
+    /// the edges. Dead cells in the pattern leave the grid untouched.
+    pub fn load_rle(&mut self, rle: &str, row: u32, col: u32) -> Result<(), Error> {
+        self.load_pattern(&parse(rle)?, row, col)
+    }
+}
@@ -0,0 +1,174 @@
+use wasm_bindgen::prelude::*;
+
+/// Source of randomness for soups and stochastic rules.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform float in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// xorshift64* generator: tiny, fast and good enough for seeding soups.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> XorShift64 {
+        // Scramble the seed with splitmix64 so small or zero seeds still give a usable state
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        XorShift64 {
+            state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z },
+        }
+    }
+}
+
+impl Rng for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// PCG-XSH-RR 64/32 generator, statistically stronger than xorshift.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+
+impl Pcg32 {
+    pub fn new(seed: u64) -> Pcg32 {
+        let mut pcg = Pcg32 {
+            state: 0,
+            increment: (0xDA3E_39CB_94B9_5BDB << 1) | 1,
+        };
+        pcg.next_u32();
+        pcg.state = pcg.state.wrapping_add(seed);
+        pcg.next_u32();
+        pcg
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(self.increment);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+}
+
+/// The browser's `Math.random()`. Not reproducible and only usable on wasm.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MathRandom;
+
+impl Rng for MathRandom {
+    fn next_u64(&mut self) -> u64 {
+        let hi = (js_sys::Math::random() * 4294967296.0) as u64;
+        let lo = (js_sys::Math::random() * 4294967296.0) as u64;
+        (hi << 32) | lo
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        js_sys::Math::random()
+    }
+}
+
+/// Which random number generator a universe draws from.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RngKind {
+    XorShift = 0,
+    Pcg = 1,
+    /// Ignores the seed; runs are never reproducible.
+    MathRandom = 2,
+}
+
+// The generator stored on a universe, kept as an enum so universes stay cloneable
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum Generator {
+    XorShift(XorShift64),
+    Pcg(Pcg32),
+    MathRandom(MathRandom),
+}
+
+impl Generator {
+    pub fn new(kind: RngKind, seed: u64) -> Generator {
+        match kind {
+            RngKind::XorShift => Generator::XorShift(XorShift64::new(seed)),
+            RngKind::Pcg => Generator::Pcg(Pcg32::new(seed)),
+            RngKind::MathRandom => Generator::MathRandom(MathRandom),
+        }
+    }
+
+    pub fn from_entropy() -> Generator {
+        Generator::new(RngKind::XorShift, entropy_seed())
+    }
+
+    pub fn kind(&self) -> RngKind {
+        match self {
+            Generator::XorShift(_) => RngKind::XorShift,
+            Generator::Pcg(_) => RngKind::Pcg,
+            Generator::MathRandom(_) => RngKind::MathRandom,
+        }
+    }
+}
+
+impl Rng for Generator {
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Generator::XorShift(rng) => rng.next_u64(),
+            Generator::Pcg(rng) => rng.next_u64(),
+            Generator::MathRandom(rng) => rng.next_u64(),
+        }
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        match self {
+            Generator::XorShift(rng) => rng.next_f64(),
+            Generator::Pcg(rng) => rng.next_f64(),
+            Generator::MathRandom(rng) => rng.next_f64(),
+        }
+    }
+}
+
+/// Picks a seed for universes that were not given one explicitly.
+#[cfg(target_arch = "wasm32")]
+pub fn entropy_seed() -> u64 {
+    MathRandom.next_u64()
+}
+
+/// Picks a seed for universes that were not given one explicitly.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn entropy_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,401 @@
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+use crate::colored::ColoredRule;
+use crate::cyclic::CyclicRule;
+use crate::engine::{Engine, Neighborhood};
+use crate::forest::ForestFire;
+use crate::hensel::IsotropicRule;
+use crate::ltl::LtlRule;
+use crate::{Cell, Error, Universe};
+
+/// Well-known birth/survival rules for `set_preset()`.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RulePreset {
+    /// Conway's Game of Life, B3/S23.
+    Life = 0,
+    /// B36/S23, home of the replicator.
+    HighLife = 1,
+    /// B2/S, where every live cell dies and most patterns explode.
+    Seeds = 2,
+    /// B3678/S34678, symmetric under swapping live and dead cells.
+    DayAndNight = 3,
+    /// B3/S12345, growing maze-like corridors.
+    Maze = 4,
+    /// B3/S45678, slowly spreading coral.
+    Coral = 5,
+    /// B4678/S35678, where blobs smooth out over time.
+    Anneal = 6,
+    /// B1357/S1357, in which every pattern is replicated.
+    Replicator = 7,
+    /// B3/S012345678, where live cells never die.
+    LifeWithoutDeath = 8,
+    /// B35678/S5678, forming large amoeba-like blobs.
+    Diamoeba = 9,
+    /// B36/S125, rich in 2x2 block patterns.
+    TwoByTwo = 10,
+    /// B368/S245, with many small spaceships.
+    Morley = 11,
+    /// B5678/S45678, Vichniac's majority vote; B5678/S56789 when counting self.
+    Vote = 12,
+}
+
+impl RulePreset {
+    fn rulestring(self) -> &'static str {
+        match self {
+            RulePreset::Life => "B3/S23",
+            RulePreset::HighLife => "B36/S23",
+            RulePreset::Seeds => "B2/S",
+            RulePreset::DayAndNight => "B3678/S34678",
+            RulePreset::Maze => "B3/S12345",
+            RulePreset::Coral => "B3/S45678",
+            RulePreset::Anneal => "B4678/S35678",
+            RulePreset::Replicator => "B1357/S1357",
+            RulePreset::LifeWithoutDeath => "B3/S012345678",
+            RulePreset::Diamoeba => "B35678/S5678",
+            RulePreset::TwoByTwo => "B36/S125",
+            RulePreset::Morley => "B368/S245",
+            RulePreset::Vote => "B5678/S45678",
+        }
+    }
+}
+
+/// An outer-totalistic two-state rule: which live-neighbor counts give birth to a
+/// dead cell and which keep a live cell alive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LifeRule {
+    // Bit `n` is set when `n` neighbors cause a birth or survival
+    birth: u16,
+    survival: u16,
+}
+
+impl LifeRule {
+    pub const CONWAY: LifeRule = LifeRule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    /// Reads `B3/S23` notation, case-insensitively and with or without the slash,
+    /// as well as the older `23/3` survival/birth form.
+    pub fn parse(rule: &str) -> Result<LifeRule, Error> {
+        let invalid = || Error::InvalidRule(rule.to_string());
+        let rule = rule.trim();
+        let mut parsed = LifeRule {
+            birth: 0,
+            survival: 0,
+        };
+
+        if rule.contains(['B', 'b', 'S', 's']) {
+            let mut section = None;
+            for c in rule.chars() {
+                match c.to_ascii_uppercase() {
+                    'B' => section = Some(&mut parsed.birth),
+                    'S' => section = Some(&mut parsed.survival),
+                    '/' => {}
+                    '0'..='9' => {
+                        let bits = section.as_deref_mut().ok_or_else(invalid)?;
+                        *bits |= 1 << c.to_digit(10).unwrap();
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+        } else {
+            let (survival, birth) = rule.split_once('/').ok_or_else(invalid)?;
+            parsed.survival = digit_bits(survival).ok_or_else(invalid)?;
+            parsed.birth = digit_bits(birth).ok_or_else(invalid)?;
+        }
+        // Survival on 9 only happens when counting the cell itself, see
+        // `set_count_self()`, but no dead cell ever sees 9
+        if parsed.born(9) {
+            return Err(invalid());
+        }
+        Ok(parsed)
+    }
+
+    pub fn born(self, neighbors: u8) -> bool {
+        self.birth & (1 << neighbors) != 0
+    }
+
+    pub fn survives(self, neighbors: u8) -> bool {
+        self.survival & (1 << neighbors) != 0
+    }
+
+    /// Every transition up front, indexed by the cell's state and then its live
+    /// neighbor count.
+    pub fn lookup(self) -> [[Cell; 10]; 2] {
+        let mut table = [[Cell::Dead; 10]; 2];
+        for n in 0..10u8 {
+            if self.born(n) {
+                table[Cell::Dead as usize][n as usize] = Cell::Alive;
+            }
+            if self.survives(n) {
+                table[Cell::Alive as usize][n as usize] = Cell::Alive;
+            }
+        }
+        table
+    }
+}
+
+/// A Generations rule: live cells that fail to survive pass through numbered decay
+/// states before dying, and only state 1 counts as a live neighbor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct GenerationsRule {
+    life: LifeRule,
+    // Total number of states, including dead and alive
+    states: u8,
+}
+
+impl GenerationsRule {
+    /// Reads `B2/S/C3` notation or Golly's older `survival/birth/states` form,
+    /// e.g. `"/2/3"` (Brian's Brain) or `"345/2/4"` (Star Wars).
+    pub fn parse(rule: &str) -> Result<GenerationsRule, Error> {
+        let invalid = || Error::InvalidRule(rule.to_string());
+        let parts: Vec<&str> = rule.trim().split('/').collect();
+        if parts.len() != 3 {
+            return Err(invalid());
+        }
+
+        let (life, states) = if rule.contains(char::is_alphabetic) {
+            let (count, rest): (Vec<&str>, Vec<&str>) = parts
+                .iter()
+                .partition(|part| part.starts_with(['C', 'c', 'G', 'g']));
+            let count = count.first().ok_or_else(invalid)?;
+            let life = LifeRule::parse(&rest.join("/")).map_err(|_| invalid())?;
+            (life, &count[1..])
+        } else {
+            let life =
+                LifeRule::parse(&format!("{}/{}", parts[0], parts[1])).map_err(|_| invalid())?;
+            (life, parts[2])
+        };
+        let states = states
+            .parse::<u8>()
+            .ok()
+            .filter(|&states| states >= 2)
+            .ok_or_else(invalid)?;
+        Ok(GenerationsRule { life, states })
+    }
+
+    pub fn state_count(self) -> u32 {
+        self.states as u32
+    }
+
+    pub fn next_state(self, state: u8, live_neighbors: u8) -> u8 {
+        match state {
+            0 if self.life.born(live_neighbors) => 1,
+            0 => 0,
+            1 if self.life.survives(live_neighbors) => 1,
+            // Decaying cells age by one each generation until they die
+            _ if state + 1 < self.states => state + 1,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for GenerationsRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/C{}", self.life, self.states)
+    }
+}
+
+// The engine for a rulestring: Generations rules have a third, state-count part,
+// Larger than Life rules are comma-separated, starting with the radius, and
+// isotropic rules have letters after their counts. Other rules may end in `V` for
+// the von Neumann neighborhood or `H` for the hexagonal one.
+fn parse_engine(rule: &str) -> Result<(Engine, Neighborhood), Error> {
+    let rule = rule.trim();
+    if rule.starts_with(['R', 'r']) && rule.contains(',') {
+        return Ok((
+            Engine::LargerThanLife(LtlRule::parse(rule)?),
+            Neighborhood::Moore,
+        ));
+    }
+
+    if rule.eq_ignore_ascii_case("WireWorld") {
+        return Ok((Engine::Wireworld, Neighborhood::Moore));
+    }
+    if let Some((rule, neighborhood)) = CyclicRule::parse(rule) {
+        return Ok((Engine::Cyclic(rule), neighborhood));
+    }
+    if let Some(rule) = ForestFire::parse(rule) {
+        return Ok((Engine::ForestFire(rule), Neighborhood::Moore));
+    }
+    let (colored, neighborhood) = split_neighborhood(rule);
+    if let Some(rule) = ColoredRule::parse(colored) {
+        return Ok((Engine::Colored(rule), neighborhood));
+    }
+
+    // Hensel's neighbor letters, which never clash with a Generations rule's `C`
+    // since those have two slashes
+    let lowercase = rule.to_ascii_lowercase();
+    if lowercase.matches('/').count() < 2 && lowercase.contains(|c| "aceijknqrtwyz-".contains(c)) {
+        let rule = IsotropicRule::parse(rule)?;
+        return Ok((Engine::Isotropic(Box::new(rule)), Neighborhood::Moore));
+    }
+
+    let (rule, neighborhood) = split_neighborhood(rule);
+    let engine = if rule.matches('/').count() == 2 {
+        Engine::Generations(GenerationsRule::parse(rule)?)
+    } else {
+        Engine::Life(LifeRule::parse(rule)?)
+    };
+    Ok((engine, neighborhood))
+}
+
+// Strips a trailing `V` or `H` neighborhood suffix
+fn split_neighborhood(rule: &str) -> (&str, Neighborhood) {
+    match rule.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('V') => (&rule[..rule.len() - 1], Neighborhood::VonNeumann),
+        Some('H') => (&rule[..rule.len() - 1], Neighborhood::Hexagonal),
+        _ => (rule, Neighborhood::Moore),
+    }
+}
+
+// Golly's notation for elementary automata, e.g. `W110`
+fn elementary_code(rule: &str) -> Option<u8> {
+    let code = rule.trim().strip_prefix(['W', 'w'])?;
+    code.parse().ok()
+}
+
+// Checks that `set_rule()` would accept `rule`
+pub(crate) fn validate(rule: &str) -> Result<(), Error> {
+    match elementary_code(rule) {
+        Some(_) => Ok(()),
+        None => parse_engine(rule).map(|_| ()),
+    }
+}
+
+// Sets bit `n` for every digit `n` from 0 to 9
+fn digit_bits(digits: &str) -> Option<u16> {
+    digits.chars().try_fold(0, |bits, c| match c {
+        '0'..='9' => Some(bits | 1 << c.to_digit(10)?),
+        _ => None,
+    })
+}
+
+fn write_digits(f: &mut fmt::Formatter, bits: u16) -> fmt::Result {
+    for n in 0..=9 {
+        if bits & (1 << n) != 0 {
+            write!(f, "{}", n)?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        write_digits(f, self.birth)?;
+        write!(f, "/S")?;
+        write_digits(f, self.survival)
+    }
+}
+
+impl Universe {
+    // Brings back the rule settings of a saved universe. Rules that `set_rule()`
+    // cannot read back, such as rule tables, kernels, ants and callbacks, are an
+    // `InvalidRule` error rather than a quiet switch to another rule.
+    pub(crate) fn restore_rule(
+        &mut self,
+        rule: &str,
+        neighborhood: Neighborhood,
+        count_self: bool,
+    ) -> Result<(), Error> {
+        self.set_rule(rule)
+            .map_err(|_| Error::InvalidRule(rule.to_string()))?;
+        self.neighborhood = neighborhood;
+        self.count_self = count_self;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches `tick()` to a birth/survival rule such as `"B3/S23"` (Conway's
+    /// Life), `"B36/S23"` (HighLife) or `"B2/S"` (Seeds). The older `"23/3"`
+    /// survival/birth form is accepted too.
+    ///
+    /// Generations rules such as `"B2/S/C3"` (or `"/2/3"`, Brian's Brain) add decay
+    /// states numbered from 2, read with `state()` and `states()`. Larger than Life
+    /// rules such as `"R5,C0,M1,S34..58,B34..45,NM"` (Bugs) count live cells within
+    /// a wider radius.
+    ///
+    /// Isotropic non-totalistic rules in Hensel notation, such as `"B2-a/S12"` (Just
+    /// Friends), tell apart arrangements of the same number of neighbors: letters
+    /// after a count allow only those arrangements and `-` before them excludes
+    /// them, using Golly's letters.
+    ///
+    /// Elementary one-dimensional rules are written `"W30"`; see `set_elementary_rule()`.
+    /// The other rules `rule()` describes in full read back too: `"WireWorld"`,
+    /// cyclic rules such as `"R1/T1/C16/NN"`, `"ForestFire p=0.01 f=0.0001"` and
+    /// colored rules such as `"Immigration"` or `"B36/S23 in 3 colors"`.
+    ///
+    /// A trailing `V`, as in `"B2/S013V"`, switches to the von Neumann neighborhood
+    /// and a trailing `H`, as in `"B2/S34H"`, to the hexagonal one; otherwise the
+    /// Moore neighborhood is used.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), Error> {
+        if let Some(code) = elementary_code(rule) {
+            self.set_elementary_rule(code);
+            return Ok(());
+        }
+
+        let (engine, neighborhood) = parse_engine(rule)?;
+        self.engine = engine;
+        self.neighborhood = neighborhood;
+        Ok(())
+    }
+
+    /// Switches to one of the named rules, e.g. for a rule dropdown.
+    pub fn set_preset(&mut self, preset: RulePreset) {
+        let rule = LifeRule::parse(preset.rulestring()).expect("presets are valid rules");
+        self.engine = Engine::Life(rule);
+        self.neighborhood = Neighborhood::Moore;
+        self.count_self = false;
+    }
+
+    /// Makes birth/survival rules count a live cell as one of its own neighbors,
+    /// so survival counts run from 1 to 9, as smoothing rules such as majority
+    /// vote are often written. `B5678/S56789` counting self is the same rule as
+    /// `B5678/S45678` without.
+    pub fn set_count_self(&mut self, count_self: bool) {
+        self.count_self = count_self;
+    }
+
+    pub fn counts_self(&self) -> bool {
+        self.count_self
+    }
+
+    /// The current rule in `B../S..` notation, or the name of a loaded rule table.
+    pub fn rule(&self) -> String {
+        let suffix = match self.neighborhood {
+            Neighborhood::Moore => "",
+            Neighborhood::VonNeumann => "V",
+            Neighborhood::Hexagonal => "H",
+        };
+        match &self.engine {
+            Engine::Life(rule) => format!("{}{}", rule, suffix),
+            Engine::Isotropic(rule) => rule.to_string(),
+            Engine::Generations(rule) => format!("{}{}", rule, suffix),
+            Engine::Colored(rule) => format!("{}{}", rule, suffix),
+            // Mirek's Cellebration notation
+            Engine::Cyclic(rule) => {
+                let neighborhood = match self.neighborhood {
+                    Neighborhood::Moore => "M",
+                    Neighborhood::VonNeumann => "N",
+                    Neighborhood::Hexagonal => "H",
+                };
+                format!("R1/T{}/C{}/N{}", rule.threshold, rule.states, neighborhood)
+            }
+            Engine::ForestFire(rule) => rule.to_string(),
+            Engine::LargerThanLife(rule) => rule.to_string(),
+            Engine::Kernel(rule) => rule.to_string(),
+            Engine::Table(table) => table.name().to_string(),
+            Engine::Wireworld => "WireWorld".to_string(),
+            Engine::Langton(langton) => langton.to_string(),
+            Engine::Elementary(ca) => format!("W{}", ca.rule),
+            Engine::Custom(_) => "custom".to_string(),
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::{Engine, MOORE, VON_NEUMANN};
+use crate::{Error, Universe};
+
+// Which cells of a transition line, after the center, are permuted into each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Symmetry {
+    None,
+    Rotate4,
+    Rotate8,
+    ReflectHorizontal,
+    Rotate4Reflect,
+    Rotate8Reflect,
+    Permute,
+}
+
+// One input or output of a transition: a fixed state or a variable
+#[derive(Clone, Copy, Debug)]
+enum Slot {
+    State(u8),
+    Var(usize),
+}
+
+impl Slot {
+    // Whether `state` fits, binding the variable on first use
+    fn accepts(self, state: u8, vars: &[Vec<u8>], bound: &mut [Option<u8>]) -> bool {
+        match self {
+            Slot::State(expected) => state == expected,
+            Slot::Var(var) => match bound[var] {
+                Some(value) => value == state,
+                None if vars[var].contains(&state) => {
+                    bound[var] = Some(state);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Transition {
+    center: Slot,
+    neighbors: Vec<Slot>,
+    output: Slot,
+}
+
+/// A multi-state rule parsed from the `@TABLE` section of a Golly `.rule` file.
+#[derive(Clone, Debug)]
+pub(crate) struct RuleTable {
+    // From the `@RULE` line; empty for a bare table
+    name: String,
+    states: u32,
+    offsets: &'static [(i32, i32)],
+    // Maps positions of a transition line onto positions of a neighborhood
+    permutations: Vec<Vec<usize>>,
+    permute: bool,
+    vars: Vec<Vec<u8>>,
+    transitions: Vec<Transition>,
+    // Results for neighborhoods seen before, keyed by center then neighbors
+    cache: HashMap<Vec<u8>, u8>,
+}
+
+fn parse_error(line: usize, message: String) -> Error {
+    Error::Parse { line, message }
+}
+
+// Rotations by multiples of `step`, optionally combined with a left-right mirror
+fn permutations(n: usize, step: usize, reflect: bool) -> Vec<Vec<usize>> {
+    let mut all = Vec::new();
+    for rotation in (0..n).step_by(step) {
+        all.push((0..n).map(|i| (i + rotation) % n).collect::<Vec<_>>());
+        if reflect {
+            all.push((0..n).map(|i| (n - i + rotation) % n).collect());
+        }
+    }
+    all
+}
+
+impl RuleTable {
+    pub fn parse(text: &str) -> Result<RuleTable, Error> {
+        // Plain `.table` files have no sections; `.rule` files keep the table under @TABLE
+        let has_sections = text.lines().any(|line| line.trim_start().starts_with('@'));
+        let mut in_table = !has_sections;
+        let mut name = String::new();
+        let mut states = None;
+        let mut neighborhood = "Moore".to_string();
+        let mut symmetry = Symmetry::None;
+        let mut var_names: Vec<String> = Vec::new();
+        let mut vars: Vec<Vec<u8>> = Vec::new();
+        let mut lines = Vec::new();
+
+        for (number, raw) in text.lines().enumerate() {
+            let number = number + 1;
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.starts_with('@') {
+                let mut words = line.split_whitespace();
+                let section = words.next();
+                if section == Some("@RULE") {
+                    name = words.next().unwrap_or("").to_string();
+                }
+                in_table = section == Some("@TABLE");
+                continue;
+            }
+            if !in_table || line.is_empty() {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "n_states" => {
+                        let count = value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|count| (2..=256).contains(count))
+                            .ok_or_else(|| {
+                                parse_error(number, format!("invalid state count {}", value))
+                            })?;
+                        states = Some(count);
+                    }
+                    "neighborhood" => neighborhood = value.to_string(),
+                    "symmetries" => {
+                        symmetry = match value {
+                            "none" => Symmetry::None,
+                            "rotate4" => Symmetry::Rotate4,
+                            "rotate8" => Symmetry::Rotate8,
+                            "reflect_horizontal" => Symmetry::ReflectHorizontal,
+                            "rotate4reflect" => Symmetry::Rotate4Reflect,
+                            "rotate8reflect" => Symmetry::Rotate8Reflect,
+                            "permute" => Symmetry::Permute,
+                            other => {
+                                return Err(parse_error(
+                                    number,
+                                    format!("unsupported symmetries \"{}\"", other),
+                                ))
+                            }
+                        }
+                    }
+                    other => {
+                        return Err(parse_error(
+                            number,
+                            format!("unknown setting \"{}\"", other),
+                        ))
+                    }
+                }
+            } else if let Some(definition) = line.strip_prefix("var ") {
+                let states =
+                    states.ok_or_else(|| parse_error(number, "var before n_states".to_string()))?;
+                let compact: String = definition.chars().filter(|c| !c.is_whitespace()).collect();
+                let (name, values) = compact
+                    .split_once('=')
+                    .and_then(|(name, values)| {
+                        let values = values.strip_prefix('{')?.strip_suffix('}')?;
+                        Some((name.to_string(), values))
+                    })
+                    .ok_or_else(|| parse_error(number, "expected var name={...}".to_string()))?;
+
+                let mut members = Vec::new();
+                for token in values.split(',') {
+                    match token.parse::<u32>() {
+                        Ok(state) if state < states => members.push(state as u8),
+                        Ok(state) => {
+                            return Err(parse_error(number, format!("no state {}", state)))
+                        }
+                        Err(_) => match var_names.iter().position(|known| known == token) {
+                            Some(var) => members.extend_from_slice(&vars[var]),
+                            None => {
+                                return Err(parse_error(
+                                    number,
+                                    format!("unknown variable \"{}\"", token),
+                                ))
+                            }
+                        },
+                    }
+                }
+                var_names.push(name);
+                vars.push(members);
+            } else {
+                lines.push((number, line.to_string()));
+            }
+        }
+
+        let states = states.ok_or_else(|| parse_error(1, "missing n_states".to_string()))?;
+        let offsets: &'static [(i32, i32)] = match neighborhood.as_str() {
+            "Moore" => &MOORE,
+            "vonNeumann" => &VON_NEUMANN,
+            other => {
+                return Err(parse_error(
+                    1,
+                    format!("unsupported neighborhood \"{}\"", other),
+                ))
+            }
+        };
+        let n = offsets.len();
+        let permutations = match symmetry {
+            Symmetry::None | Symmetry::Permute => permutations(n, n, false),
+            Symmetry::Rotate4 => permutations(n, n / 4, false),
+            Symmetry::Rotate8 if n == 8 => permutations(n, 1, false),
+            Symmetry::ReflectHorizontal => permutations(n, n, true),
+            Symmetry::Rotate4Reflect => permutations(n, n / 4, true),
+            Symmetry::Rotate8Reflect if n == 8 => permutations(n, 1, true),
+            _ => {
+                return Err(parse_error(
+                    1,
+                    "rotate8 needs the Moore neighborhood".to_string(),
+                ))
+            }
+        };
+
+        let mut transitions = Vec::new();
+        for (number, line) in lines {
+            // Single-digit states may be written without commas
+            let tokens: Vec<String> = if line.contains(',') {
+                line.split(',')
+                    .map(|token| token.trim().to_string())
+                    .collect()
+            } else {
+                line.chars().map(|c| c.to_string()).collect()
+            };
+            if tokens.len() != n + 2 {
+                return Err(parse_error(
+                    number,
+                    format!("expected {} entries but got {}", n + 2, tokens.len()),
+                ));
+            }
+
+            let slots = tokens
+                .iter()
+                .map(|token| match token.parse::<u32>() {
+                    Ok(state) if state < states => Ok(Slot::State(state as u8)),
+                    Ok(state) => Err(parse_error(number, format!("no state {}", state))),
+                    Err(_) => var_names
+                        .iter()
+                        .position(|known| known == token)
+                        .map(Slot::Var)
+                        .ok_or_else(|| {
+                            parse_error(number, format!("unknown variable \"{}\"", token))
+                        }),
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let output = slots[n + 1];
+            if let Slot::Var(var) = output {
+                let bound = slots[..=n]
+                    .iter()
+                    .any(|slot| matches!(slot, Slot::Var(input) if *input == var));
+                if !bound {
+                    return Err(parse_error(
+                        number,
+                        "output variable does not appear in the inputs".to_string(),
+                    ));
+                }
+            }
+            transitions.push(Transition {
+                center: slots[0],
+                neighbors: slots[1..=n].to_vec(),
+                output,
+            });
+        }
+
+        Ok(RuleTable {
+            name,
+            states,
+            offsets,
+            permutations,
+            permute: symmetry == Symmetry::Permute,
+            vars,
+            transitions,
+            cache: HashMap::new(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state_count(&self) -> u32 {
+        self.states
+    }
+
+    pub fn offsets(&self) -> &'static [(i32, i32)] {
+        self.offsets
+    }
+
+    // The first matching transition decides; cells no transition matches keep their state
+    pub fn next_state(&mut self, center: u8, neighbors: &[u8]) -> u8 {
+        let mut key = Vec::with_capacity(neighbors.len() + 1);
+        key.push(center);
+        key.extend_from_slice(neighbors);
+        if let Some(&state) = self.cache.get(&key) {
+            return state;
+        }
+
+        let state = self
+            .transitions
+            .iter()
+            .find_map(|transition| self.apply(transition, center, neighbors))
+            .unwrap_or(center);
+        self.cache.insert(key, state);
+        state
+    }
+
+    fn apply(&self, transition: &Transition, center: u8, neighbors: &[u8]) -> Option<u8> {
+        let mut bound = vec![None; self.vars.len()];
+        if !transition.center.accepts(center, &self.vars, &mut bound) {
+            return None;
+        }
+
+        let matched = if self.permute {
+            let mut used = vec![false; neighbors.len()];
+            self.assign(&transition.neighbors, neighbors, &mut used, &mut bound)
+        } else {
+            self.permutations.iter().any(|permutation| {
+                let mut attempt = bound.clone();
+                let fits = transition
+                    .neighbors
+                    .iter()
+                    .zip(permutation.iter())
+                    .all(|(slot, &i)| slot.accepts(neighbors[i], &self.vars, &mut attempt));
+                if fits {
+                    bound = attempt;
+                }
+                fits
+            })
+        };
+        if !matched {
+            return None;
+        }
+
+        match transition.output {
+            Slot::State(state) => Some(state),
+            Slot::Var(var) => bound[var],
+        }
+    }
+
+    // Matches slots to neighbors in any order, backtracking over variable bindings
+    fn assign(
+        &self,
+        slots: &[Slot],
+        neighbors: &[u8],
+        used: &mut [bool],
+        bound: &mut Vec<Option<u8>>,
+    ) -> bool {
+        let Some((&slot, rest)) = slots.split_first() else {
+            return true;
+        };
+        for i in 0..neighbors.len() {
+            if used[i] {
+                continue;
+            }
+            let before = bound.clone();
+            if slot.accepts(neighbors[i], &self.vars, bound) {
+                used[i] = true;
+                if self.assign(rest, neighbors, used, bound) {
+                    return true;
+                }
+                used[i] = false;
+            }
+            *bound = before;
+        }
+        false
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to a multi-state rule from a Golly `.rule` file (its `@TABLE` section)
+    /// or a bare `.table` file. Moore and von Neumann neighborhoods are supported;
+    /// states beyond alive/dead are read and written with `state()` and `set_state()`.
+    pub fn set_rule_table(&mut self, text: &str) -> Result<(), Error> {
+        let table = RuleTable::parse(text)?;
+        self.engine = Engine::Table(Box::new(table));
+        Ok(())
+    }
+}
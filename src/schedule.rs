@@ -0,0 +1,60 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{rule, Error, Universe};
+
+// Rules taking turns, each for a fixed number of generations, looping forever
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Schedule {
+    entries: Vec<(String, u32)>,
+    // Entry in effect and how many of its generations have run
+    position: usize,
+    elapsed: u32,
+}
+
+impl Universe {
+    // Switches to the scheduled rule when its turn starts, then counts the tick
+    pub(crate) fn advance_schedule(&mut self) {
+        let Some(schedule) = &mut self.schedule else {
+            return;
+        };
+        let (rule, generations) = schedule.entries[schedule.position].clone();
+        let starting = schedule.elapsed == 0;
+        schedule.elapsed += 1;
+        if schedule.elapsed >= generations {
+            schedule.elapsed = 0;
+            schedule.position = (schedule.position + 1) % schedule.entries.len();
+        }
+
+        if starting {
+            self.set_rule(&rule).expect("scheduled rules are validated");
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Appends a turn to the rule schedule: from the next tick on, the rules added
+    /// here run in order for their `generations` each, looping back to the first,
+    /// e.g. `"B3/S23"` for 100 then `"B2/S"` for 10. Accepts anything `set_rule()`
+    /// does.
+    pub fn add_scheduled_rule(&mut self, rule: &str, generations: u32) -> Result<(), Error> {
+        rule::validate(rule)?;
+        let schedule = self.schedule.get_or_insert_with(Schedule::default);
+        schedule
+            .entries
+            .push((rule.to_string(), generations.max(1)));
+        Ok(())
+    }
+
+    /// Drops the rule schedule, leaving whichever rule is in effect running.
+    pub fn clear_schedule(&mut self) {
+        self.schedule = None;
+    }
+
+    /// Number of turns in the rule schedule.
+    pub fn schedule_len(&self) -> usize {
+        self.schedule
+            .as_ref()
+            .map_or(0, |schedule| schedule.entries.len())
+    }
+}
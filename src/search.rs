@@ -0,0 +1,124 @@
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+use crate::apgcode;
+use crate::engine::Engine;
+use crate::pattern::{self, Orientation, Pattern};
+use crate::rule::LifeRule;
+use crate::{patterns, Boundary, Cell, Error, Neighborhood, SparseUniverse, Universe};
+
+impl Universe {
+    // A cell by signed coordinates, wrapping or reading dead past the edges by boundary
+    fn cell_at(&self, row: i64, col: i64) -> Cell {
+        let (height, width) = (self.height as i64, self.width as i64);
+        let (row, col) = match self.boundary {
+            Boundary::Wrap => (row.rem_euclid(height), col.rem_euclid(width)),
+            Boundary::Fixed if row < 0 || col < 0 || row >= height || col >= width => {
+                return Cell::Dead
+            }
+            Boundary::Fixed => (row, col),
+        };
+        self.cells[self.get_index(row as u32, col as u32)]
+    }
+
+    // Whether `pattern` sits at `row`, `col` exactly, with a dead one-cell ring around it
+    fn matches_at(&self, pattern: &[Vec<bool>], row: u32, col: u32) -> bool {
+        let height = pattern.len() as i64;
+        let width = pattern.first().map_or(0, |r| r.len()) as i64;
+        for dr in -1..=height {
+            for dc in -1..=width {
+                let expected = dr >= 0
+                    && dc >= 0
+                    && dr < height
+                    && dc < width
+                    && pattern[dr as usize][dc as usize];
+                let alive = self.cell_at(row as i64 + dr, col as i64 + dc) == Cell::Alive;
+                if alive != expected {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+// The pattern as rows of live flags over its bounding box
+fn to_grid(pattern: &Pattern) -> Vec<Vec<bool>> {
+    let mut grid = vec![vec![false; pattern.width as usize]; pattern.height as usize];
+    for &(row, col) in pattern.cells.iter() {
+        grid[row as usize][col as usize] = true;
+    }
+    grid
+}
+
+// Every phase of `pattern` if it repeats under Conway's Life, otherwise just itself
+fn phases(pattern: Pattern) -> Vec<Pattern> {
+    let mut universe = SparseUniverse::new();
+    for &(row, col) in pattern.cells.iter() {
+        match (i32::try_from(row), i32::try_from(col)) {
+            (Ok(row), Ok(col)) => universe.set_alive(row, col),
+            _ => return vec![pattern],
+        }
+    }
+    match apgcode::cycle(universe) {
+        Ok(cycle) => cycle.phases,
+        Err(_) => vec![pattern],
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Top-left corners, as flat `[row, col, ...]` pairs, of every place where
+    /// `pattern` occurs as an isolated object: its bounding box matches exactly and
+    /// the ring of cells around it is dead. Edges wrap or read dead by boundary.
+    ///
+    /// `pattern` is a pattern name or text in any format `load_any()` accepts. With
+    /// `any_orientation` rotated and mirrored copies are found too. While the
+    /// universe runs Conway's Life, every phase of an oscillator or spaceship
+    /// matches, so searching for `"glider"` finds gliders in any phase; under other
+    /// rules only the phase given is matched.
+    pub fn find_pattern(&self, pattern: &str, any_orientation: bool) -> Result<Vec<u32>, Error> {
+        let pattern = match patterns::lookup(pattern) {
+            Some(pattern) => pattern,
+            None => pattern::parse_any(pattern)?,
+        };
+        if pattern.cells.is_empty() {
+            return Err(Error::NoLiveCells);
+        }
+
+        let orientations = if any_orientation {
+            &Orientation::ALL[..]
+        } else {
+            &Orientation::ALL[..1]
+        };
+        let conway = matches!(self.engine, Engine::Life(rule) if rule == LifeRule::CONWAY)
+            && self.neighborhood == Neighborhood::Moore
+            && !self.count_self;
+        let phases = if conway {
+            phases(pattern)
+        } else {
+            vec![pattern]
+        };
+
+        let mut grids: Vec<Vec<Vec<bool>>> = Vec::new();
+        for phase in phases.iter() {
+            for &orientation in orientations {
+                let grid = to_grid(&phase.oriented(orientation));
+                if !grids.contains(&grid) {
+                    grids.push(grid);
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if grids.iter().any(|grid| self.matches_at(grid, row, col)) {
+                    found.push(row);
+                    found.push(col);
+                }
+            }
+        }
+        Ok(found)
+    }
+}
@@ -0,0 +1,180 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Error, Universe};
+
+// Selected rectangle, already clipped to the grid when it was made
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Selection {
+    row: u32,
+    col: u32,
+    height: u32,
+    width: u32,
+}
+
+impl Universe {
+    // The current selection re-clipped to the grid, which may have been resized since
+    fn selected(&self) -> Result<Selection, Error> {
+        let selection = self.selection.ok_or(Error::NoSelection)?;
+        let (rows, cols) = self.clip_region(
+            selection.row,
+            selection.col,
+            selection.height,
+            selection.width,
+        );
+        if rows.is_empty() || cols.is_empty() {
+            return Err(Error::NoSelection);
+        }
+        Ok(Selection {
+            row: rows.start,
+            col: cols.start,
+            height: rows.len() as u32,
+            width: cols.len() as u32,
+        })
+    }
+
+    // Row-major copy of the selected cells
+    fn selected_cells(&self, selection: Selection) -> Vec<Cell> {
+        let mut cells = Vec::with_capacity((selection.height * selection.width) as usize);
+        for r in 0..selection.height {
+            for c in 0..selection.width {
+                cells.push(self.cells[self.get_index(selection.row + r, selection.col + c)]);
+            }
+        }
+        cells
+    }
+
+    // Rewrites the selection as a `height` x `width` block at the same top-left corner,
+    // taking each cell from `source(row, col)` into the old contents
+    fn rearrange_selection(
+        &mut self,
+        height: u32,
+        width: u32,
+        source: impl Fn(u32, u32) -> usize,
+    ) -> Result<(), Error> {
+        let selection = self.selected()?;
+        let old = self.selected_cells(selection);
+        self.record_edit();
+        self.fill_region(
+            selection.row,
+            selection.col,
+            selection.height,
+            selection.width,
+            Cell::Dead,
+        );
+
+        let (rows, cols) = self.clip_region(selection.row, selection.col, height, width);
+        for r in rows.clone() {
+            for c in cols.clone() {
+                let idx = self.get_index(r, c);
+                self.cells[idx] = old[source(r - selection.row, c - selection.col)];
+            }
+        }
+        self.select(selection.row, selection.col, height, width);
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Selects a `height` x `width` rectangle, clipped to the grid. An empty
+    /// rectangle clears the selection.
+    pub fn select(&mut self, row: u32, col: u32, height: u32, width: u32) {
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        self.selection = if rows.is_empty() || cols.is_empty() {
+            None
+        } else {
+            Some(Selection {
+                row: rows.start,
+                col: cols.start,
+                height: rows.len() as u32,
+                width: cols.len() as u32,
+            })
+        };
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0, 0, self.height, self.width);
+    }
+
+    pub fn deselect(&mut self) {
+        self.selection = None;
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.selected().is_ok()
+    }
+
+    /// The selection as `[row, col, height, width]`, or an empty array if there is none.
+    pub fn selection(&self) -> Vec<u32> {
+        match self.selected() {
+            Ok(s) => vec![s.row, s.col, s.height, s.width],
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Kills every selected cell.
+    pub fn clear_selection(&mut self) -> Result<(), Error> {
+        let s = self.selected()?;
+        self.record_edit();
+        self.fill_region(s.row, s.col, s.height, s.width, Cell::Dead);
+        Ok(())
+    }
+
+    /// Flips every selected cell between alive and dead.
+    pub fn invert_selection(&mut self) -> Result<(), Error> {
+        let s = self.selected()?;
+        self.invert_region(s.row, s.col, s.height, s.width);
+        Ok(())
+    }
+
+    /// Copies the selection into a new universe, as `copy_region()` does.
+    pub fn copy_selection(&self) -> Result<Universe, Error> {
+        let s = self.selected()?;
+        self.copy_region(s.row, s.col, s.height, s.width)
+    }
+
+    /// Live cells of the selection as flat `[row, col, ...]` pairs relative to its
+    /// top-left corner.
+    pub fn selection_cells(&self) -> Result<Vec<u32>, Error> {
+        let s = self.selected()?;
+        let mut live = Vec::new();
+        for (i, cell) in self.selected_cells(s).into_iter().enumerate() {
+            if cell == Cell::Alive {
+                live.push(i as u32 / s.width);
+                live.push(i as u32 % s.width);
+            }
+        }
+        Ok(live)
+    }
+
+    /// The selection as RLE, for copying to the clipboard or to Golly.
+    pub fn selection_to_rle(&self) -> Result<String, Error> {
+        let s = self.selected()?;
+        Ok(self.region_to_rle(s.row, s.col, s.height, s.width))
+    }
+
+    /// Mirrors the selected cells left to right.
+    pub fn flip_selection_horizontal(&mut self) -> Result<(), Error> {
+        let s = self.selected()?;
+        self.rearrange_selection(s.height, s.width, |r, c| {
+            (r * s.width + (s.width - 1 - c)) as usize
+        })
+    }
+
+    /// Mirrors the selected cells top to bottom.
+    pub fn flip_selection_vertical(&mut self) -> Result<(), Error> {
+        let s = self.selected()?;
+        self.rearrange_selection(s.height, s.width, |r, c| {
+            ((s.height - 1 - r) * s.width + c) as usize
+        })
+    }
+
+    /// Rotates the selected cells a quarter turn clockwise, keeping the top-left
+    /// corner in place. The selection takes the rotated shape, clipped to the grid.
+    pub fn rotate_selection_cw(&mut self) -> Result<(), Error> {
+        let s = self.selected()?;
+        self.rearrange_selection(s.width, s.height, |r, c| {
+            ((s.height - 1 - c) * s.width + r) as usize
+        })
+    }
+}
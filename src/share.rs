@@ -0,0 +1,102 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{rle, Boundary, Error, Universe};
+
+// URL-safe base64 alphabet (RFC 4648 section 5), written without padding
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// First character of a share string, saying how the rest is encoded
+const BINARY: char = 'b';
+const RLE: char = 'r';
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    text
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, Error> {
+    let values = text
+        .bytes()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .map(|v| v as u32)
+                .ok_or_else(|| invalid(&format!("unexpected character '{}'", c as char)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if values.len() % 4 == 1 {
+        return Err(invalid("truncated base64"));
+    }
+
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &v)| n | v << (18 - 6 * i));
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidData(message.to_string())
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// A short URL-safe string holding the grid, for sharing a board in a link hash.
+    ///
+    /// Uses base64 of either the `to_bytes()` snapshot or the grid's RLE, whichever is
+    /// shorter. RLE keeps the rule but not the layers or boundary, so it is only
+    /// used for sparse boards that do without them.
+    pub fn to_share_string(&self) -> String {
+        let binary = encode_base64(&self.to_bytes());
+        let rle = encode_base64(self.to_rle().as_bytes());
+        let layers_in_use = self.flags.iter().any(|&flags| flags != 0)
+            || !self.tags.is_empty()
+            || !self.states.is_empty();
+        let plain = !layers_in_use && !self.count_self && self.boundary == Boundary::Wrap;
+        if rle.len() < binary.len() && plain {
+            format!("{}{}", RLE, rle)
+        } else {
+            format!("{}{}", BINARY, binary)
+        }
+    }
+
+    /// Restores a universe from `to_share_string()`.
+    pub fn from_share_string(share: &str) -> Result<Universe, Error> {
+        let mut chars = share.chars();
+        match chars.next() {
+            Some(BINARY) => Universe::from_bytes(&decode_base64(chars.as_str())?),
+            Some(RLE) => {
+                let bytes = decode_base64(chars.as_str())?;
+                let text = String::from_utf8(bytes).map_err(|_| invalid("RLE is not UTF-8"))?;
+                let pattern = rle::parse(&text)?;
+                let mut universe =
+                    Universe::blank(pattern.width, pattern.height, Generator::from_entropy())?;
+                universe.stamp(0, 0, &pattern.cells);
+                if let Some(rule) = pattern.rule.as_deref() {
+                    // Unsupported rules fall back to Conway's Life
+                    let _ = universe.set_rule(rule);
+                }
+                universe.save_initial();
+                Ok(universe)
+            }
+            _ => Err(invalid("unknown share string encoding")),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Error, Rng, Universe};
+
+/// Symmetry groups for generated soups, named as in apgsearch.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// Invariant under 180° rotation.
+    C2 = 0,
+    /// Invariant under 90° rotation. Needs a square universe.
+    C4 = 1,
+    /// Mirrored left to right.
+    D2 = 2,
+    /// Mirrored left to right and top to bottom.
+    D4 = 3,
+    /// Invariant under every rotation and reflection. Needs a square universe.
+    D8 = 4,
+}
+
+/// Direction in which a density gradient varies.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gradient {
+    /// From the left edge to the right edge.
+    Horizontal = 0,
+    /// From the top edge to the bottom edge.
+    Vertical = 1,
+    /// From the center out to the corners.
+    Radial = 2,
+}
+
+impl Symmetry {
+    fn needs_square(self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+}
+
+impl Universe {
+    // Every position the symmetry maps (row, col) to, including itself
+    fn symmetric_images(&self, symmetry: Symmetry, row: u32, col: u32) -> Vec<(u32, u32)> {
+        let last_row = self.height - 1;
+        let last_col = self.width - 1;
+        let half_turn = (last_row - row, last_col - col);
+
+        match symmetry {
+            Symmetry::C2 => vec![(row, col), half_turn],
+            Symmetry::C4 => vec![
+                (row, col),
+                (col, last_col - row),
+                half_turn,
+                (last_row - col, row),
+            ],
+            Symmetry::D2 => vec![(row, col), (row, last_col - col)],
+            Symmetry::D4 => vec![
+                (row, col),
+                (row, last_col - col),
+                (last_row - row, col),
+                half_turn,
+            ],
+            Symmetry::D8 => vec![
+                (row, col),
+                (col, last_col - row),
+                half_turn,
+                (last_row - col, row),
+                (row, last_col - col),
+                (last_row - row, col),
+                (col, row),
+                (last_row - col, last_col - row),
+            ],
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Refills the grid with random soup that has the given symmetry.
+    pub fn randomize_symmetric(&mut self, density: f64, symmetry: Symmetry) -> Result<(), Error> {
+        if symmetry.needs_square() && self.width != self.height {
+            return Err(Error::NotSquare {
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        self.record_edit();
+        self.fill_random(density);
+
+        // Copy each cell from the lowest-indexed cell in its orbit, which is never overwritten
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let source = self
+                    .symmetric_images(symmetry, row, col)
+                    .into_iter()
+                    .map(|(r, c)| self.get_index(r, c))
+                    .min()
+                    .unwrap();
+                let idx = self.get_index(row, col);
+                self.cells[idx] = self.cells[source];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Refills the grid with soup whose density blends from `start` to `end`.
+    pub fn randomize_gradient(&mut self, start: f64, end: f64, gradient: Gradient) {
+        self.record_edit();
+        let span = |n: u32| (n.max(2) - 1) as f64;
+        let center_row = (self.height - 1) as f64 / 2.0;
+        let center_col = (self.width - 1) as f64 / 2.0;
+        let max_distance = center_row.hypot(center_col).max(f64::MIN_POSITIVE);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let t = match gradient {
+                    Gradient::Horizontal => col as f64 / span(self.width),
+                    Gradient::Vertical => row as f64 / span(self.height),
+                    Gradient::Radial => {
+                        (row as f64 - center_row).hypot(col as f64 - center_col) / max_distance
+                    }
+                };
+                let density = start + (end - start) * t;
+
+                let idx = self.get_index(row, col);
+                self.cells[idx] = if self.rng.next_f64() < density {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+            }
+        }
+    }
+
+    /// Refills only the given rectangle with random soup, leaving the rest intact.
+    ///
+    /// Parts of the rectangle outside the grid are ignored.
+    pub fn randomize_region(&mut self, row: u32, col: u32, height: u32, width: u32, density: f64) {
+        self.record_edit();
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        for r in rows {
+            for c in cols.clone() {
+                let idx = self.get_index(r, c);
+                self.cells[idx] = if self.rng.next_f64() < density {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+            }
+        }
+    }
+}
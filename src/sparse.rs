@@ -0,0 +1,120 @@
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+
+use crate::{rle, Error};
+
+/// Unbounded universe that only stores live cells, so patterns can travel
+/// indefinitely instead of wrapping around a torus.
+///
+/// Coordinates are signed and may grow in any direction.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseUniverse {
+    live: HashSet<(i32, i32)>,
+    generation: u32,
+}
+
+#[wasm_bindgen]
+impl SparseUniverse {
+    pub fn new() -> SparseUniverse {
+        SparseUniverse::default()
+    }
+
+    /// Decodes an RLE pattern with its top-left corner at the origin.
+    pub fn from_rle(rle: &str) -> Result<SparseUniverse, Error> {
+        let pattern = rle::parse(rle)?;
+        let mut universe = SparseUniverse::new();
+        for (row, col) in pattern.cells {
+            universe.set_alive(row as i32, col as i32);
+        }
+        Ok(universe)
+    }
+
+    pub fn is_alive(&self, row: i32, col: i32) -> bool {
+        self.live.contains(&(row, col))
+    }
+
+    pub fn set_alive(&mut self, row: i32, col: i32) {
+        self.live.insert((row, col));
+    }
+
+    pub fn set_dead(&mut self, row: i32, col: i32) {
+        self.live.remove(&(row, col));
+    }
+
+    pub fn toggle_cell(&mut self, row: i32, col: i32) {
+        if !self.live.remove(&(row, col)) {
+            self.live.insert((row, col));
+        }
+    }
+
+    /// Sets every `[row, col]` pair of a flat coordinate array alive.
+    pub fn set_cells(&mut self, coords: &[i32]) {
+        for pair in coords.chunks_exact(2) {
+            self.set_alive(pair[0], pair[1]);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.live.clear();
+    }
+
+    pub fn population(&self) -> u32 {
+        self.live.len() as u32
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Live cells as a flat `[row, col, ...]` array sorted by row, then column.
+    pub fn live_cells(&self) -> Vec<i32> {
+        let mut cells: Vec<_> = self.live.iter().cloned().collect();
+        cells.sort_unstable();
+        cells.into_iter().flat_map(|(r, c)| [r, c]).collect()
+    }
+
+    /// Bounding box of the live cells as `[min_row, min_col, max_row, max_col]`,
+    /// or an empty array when nothing is alive.
+    pub fn bounds(&self) -> Vec<i32> {
+        let mut cells = self.live.iter();
+        let first = match cells.next() {
+            Some(&cell) => cell,
+            None => return Vec::new(),
+        };
+
+        let (mut min_row, mut min_col, mut max_row, mut max_col) =
+            (first.0, first.1, first.0, first.1);
+        for &(row, col) in cells {
+            min_row = min_row.min(row);
+            min_col = min_col.min(col);
+            max_row = max_row.max(row);
+            max_col = max_col.max(col);
+        }
+        vec![min_row, min_col, max_row, max_col]
+    }
+
+    pub fn tick(&mut self) {
+        // Only cells next to a live cell can be alive next generation
+        let mut counts: HashMap<(i32, i32), u8> = HashMap::with_capacity(self.live.len() * 8);
+        for &(row, col) in self.live.iter() {
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    if delta_row == 0 && delta_col == 0 {
+                        continue;
+                    }
+                    let neighbor = (row.wrapping_add(delta_row), col.wrapping_add(delta_col));
+                    *counts.entry(neighbor).or_insert(0) += 1;
+                }
+            }
+        }
+
+        self.live = counts
+            .into_iter()
+            .filter(|&(cell, count)| count == 3 || (count == 2 && self.live.contains(&cell)))
+            .map(|(cell, _)| cell)
+            .collect();
+        self.generation += 1;
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+use wasm_bindgen::prelude::*;
+
+use crate::{Rng, Universe, ANCHOR, WALL};
+
+impl Universe {
+    // Whether an event of probability `chance` happens; certain events leave the
+    // generator untouched so deterministic runs stay reproducible
+    pub(crate) fn roll(&mut self, chance: f64) -> bool {
+        chance >= 1.0 || (chance > 0.0 && self.rng.next_f64() < chance)
+    }
+
+    // Flips each cell with probability `noise`, jumping straight to the next flipped
+    // cell with geometrically distributed gaps, and folds the flips into `changed`.
+    // Walls and anchors keep their state.
+    pub(crate) fn apply_noise(&mut self) {
+        if self.noise <= 0.0 {
+            return;
+        }
+
+        let mut changed: BTreeSet<usize> = self
+            .changed
+            .chunks_exact(2)
+            .map(|pair| self.get_index(pair[0], pair[1]))
+            .collect();
+        // `ln_1p` keeps tiny probabilities from rounding the log to 0
+        let log_miss = (-self.noise).ln_1p();
+        let mut idx = 0usize;
+        loop {
+            if self.noise < 1.0 {
+                let gap = ((1.0 - self.rng.next_f64()).ln() / log_miss).floor();
+                idx = idx.saturating_add(gap as usize);
+            }
+            if idx >= self.cells.len() {
+                break;
+            }
+            if self.flags[idx] & (WALL | ANCHOR) != 0 {
+                idx += 1;
+                continue;
+            }
+            self.cells[idx].toggle();
+            if !changed.remove(&idx) {
+                changed.insert(idx);
+            }
+            idx += 1;
+        }
+
+        let width = self.width as usize;
+        self.changed = changed
+            .into_iter()
+            .flat_map(|idx| [(idx / width) as u32, (idx % width) as u32])
+            .collect();
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Makes birth/survival rules probabilistic: a birth the rule calls for happens
+    /// with probability `birth` and a survival with probability `survival`, drawn
+    /// from the universe's generator. `1, 1` restores the deterministic rule.
+    pub fn set_rule_probabilities(&mut self, birth: f64, survival: f64) {
+        self.birth_chance = birth.clamp(0.0, 1.0);
+        self.survival_chance = survival.clamp(0.0, 1.0);
+    }
+
+    pub fn birth_probability(&self) -> f64 {
+        self.birth_chance
+    }
+
+    pub fn survival_probability(&self) -> f64 {
+        self.survival_chance
+    }
+
+    /// Sets a "temperature": after every `tick()` each cell flips between alive and
+    /// dead with probability `p`, drawn from the universe's generator. Walls and
+    /// anchors are left alone. 0, or a value that isn't finite, turns it off.
+    pub fn set_noise(&mut self, p: f64) {
+        self.noise = if p.is_finite() {
+            p.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+    }
+
+    pub fn noise(&self) -> f64 {
+        self.noise
+    }
+}
@@ -0,0 +1,70 @@
+use wasm_bindgen::prelude::*;
+
+use crate::engine::Engine;
+use crate::{Cell, Rng, Universe};
+
+/// Side in a two-player game; see `start_battle()`.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Team {
+    A = 1,
+    B = 2,
+}
+
+impl Universe {
+    // State the live cell at `idx` will have once the state layer is synced
+    fn live_state(&self, idx: usize) -> u8 {
+        match self.states.get(idx) {
+            Some(&state) if state != 0 && (state as u32) < self.state_count() => state,
+            _ => 1,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Turns the universe into a battle between two teams under the current
+    /// birth/survival rule, Immigration for Conway's Life: survivors stay on their
+    /// team and newborns join the team most of their parents are on. Cells already
+    /// alive join team A. Teams are the states 1 and 2 of `states()`.
+    pub fn start_battle(&mut self) {
+        self.set_colors(2).expect("two colors are always allowed");
+    }
+
+    /// Replaces a `height` x `width` rectangle whose top-left corner is at `row`,
+    /// `col` with random cells of `team`, each alive with probability `density`.
+    /// Starts a battle first if none is running.
+    pub fn seed_team(
+        &mut self,
+        team: Team,
+        row: u32,
+        col: u32,
+        height: u32,
+        width: u32,
+        density: f64,
+    ) {
+        if !matches!(self.engine, Engine::Colored(_)) {
+            self.start_battle();
+        }
+        self.record_edit();
+        self.sync_states();
+        let (rows, cols) = self.clip_region(row, col, height, width);
+        for r in rows {
+            for c in cols.clone() {
+                let idx = self.get_index(r, c);
+                let alive = self.rng.next_f64() < density;
+                self.cells[idx] = if alive { Cell::Alive } else { Cell::Dead };
+                self.states[idx] = if alive { team as u8 } else { 0 };
+            }
+        }
+    }
+
+    /// Number of live cells on `team`, for keeping score.
+    pub fn team_population(&self, team: Team) -> u32 {
+        (0..self.cells.len())
+            .filter(|&idx| self.cells[idx] == Cell::Alive && self.live_state(idx) == team as u8)
+            .count() as u32
+    }
+}
@@ -0,0 +1,52 @@
+use wasm_bindgen::prelude::*;
+
+use crate::rng::Generator;
+use crate::{Cell, Error, Universe};
+
+#[wasm_bindgen]
+impl Universe {
+    /// Parses a grid as produced by `render()` (`◻`/`◼`), or a plain `.`/`O` grid.
+    ///
+    /// Blank lines are skipped and short rows are padded with dead cells.
+    pub fn from_text(text: &str) -> Result<Universe, Error> {
+        let rows = parse_rows(text)?;
+        let height = rows.len() as u32;
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0) as u32;
+
+        let mut universe = Universe::blank(width, height, Generator::from_entropy())?;
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                let idx = universe.get_index(row as u32, col as u32);
+                universe.cells[idx] = cell;
+            }
+        }
+        universe.save_initial();
+        Ok(universe)
+    }
+}
+
+fn parse_rows(text: &str) -> Result<Vec<Vec<Cell>>, Error> {
+    let mut rows = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        let row = line
+            .chars()
+            .map(|c| match c {
+                '◻' | '.' => Ok(Cell::Dead),
+                '◼' | 'O' | 'o' | '*' => Ok(Cell::Alive),
+                other => Err(Error::Parse {
+                    line: line_no + 1,
+                    message: format!("unexpected character '{}'", other),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
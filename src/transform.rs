@@ -0,0 +1,210 @@
+use wasm_bindgen::prelude::*;
+
+use crate::elementary::Elementary;
+use crate::engine::Engine;
+use crate::{check_size, Cell, Error, Universe};
+
+/// Which part of the grid stays in place when resizing.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    TopLeft = 0,
+    TopRight = 1,
+    BottomLeft = 2,
+    BottomRight = 3,
+    Center = 4,
+}
+
+impl Anchor {
+    // Where the old grid starts inside the new one along each axis
+    fn offsets(self, old: (u32, u32), new: (u32, u32)) -> (i64, i64) {
+        let grow_rows = new.0 as i64 - old.0 as i64;
+        let grow_cols = new.1 as i64 - old.1 as i64;
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopRight => (0, grow_cols),
+            Anchor::BottomLeft => (grow_rows, 0),
+            Anchor::BottomRight => (grow_rows, grow_cols),
+            Anchor::Center => (grow_rows / 2, grow_cols / 2),
+        }
+    }
+}
+
+impl Universe {
+    /// Bounding box of the live cells as `(min_row, min_col, max_row, max_col)`.
+    pub(crate) fn live_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.cells[self.get_index(row, col)] == Cell::Alive {
+                    bounds = Some(match bounds {
+                        None => (row, col, row, col),
+                        Some((r0, c0, r1, c1)) => {
+                            (r0.min(row), c0.min(col), r1.max(row), c1.max(col))
+                        }
+                    });
+                }
+            }
+        }
+        bounds
+    }
+
+    // Rebuilds the grid at a new size, taking each new cell from the old
+    // coordinates returned by `source`, or leaving it dead for `None`
+    pub(crate) fn remap<F>(&mut self, width: u32, height: u32, source: F)
+    where
+        F: Fn(u32, u32) -> Option<(u32, u32)>,
+    {
+        let sources: Vec<Option<usize>> = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| source(row, col).map(|(r, c)| self.get_index(r, c)))
+            .collect();
+
+        if let Engine::Langton(langton) = &mut self.engine {
+            let mut targets = vec![None; self.cells.len()];
+            for (target, source) in sources.iter().enumerate() {
+                if let Some(source) = *source {
+                    targets[source] = Some(target);
+                }
+            }
+            langton.remap(self.width, width, &targets);
+        }
+
+        remap_layer(&mut self.cells, &sources, Cell::Dead);
+        remap_layer(&mut self.flags, &sources, 0);
+        if !self.tags.is_empty() {
+            remap_layer(&mut self.tags, &sources, 0);
+        }
+        if !self.states.is_empty() {
+            remap_layer(&mut self.states, &sources, 0);
+        }
+        self.width = width;
+        self.height = height;
+
+        // The newest generation of a 1D rule may have moved
+        if let Engine::Elementary(ca) = self.engine {
+            let row = self.newest_row();
+            self.engine = Engine::Elementary(Elementary { row, ..ca });
+        }
+    }
+}
+
+// Rebuilds one per-cell layer from source indices
+fn remap_layer<T: Copy>(layer: &mut Vec<T>, sources: &[Option<usize>], empty: T) {
+    let mut next = Vec::with_capacity(layer.capacity().max(sources.len()));
+    next.extend(sources.iter().map(|source| match source {
+        Some(idx) => layer[*idx],
+        None => empty,
+    }));
+
+    // Reuse the existing buffer when the size is unchanged so pointers stay valid
+    if next.len() == layer.len() {
+        layer.copy_from_slice(&next);
+    } else {
+        *layer = next;
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Translates every cell by `d_row` rows and `d_col` columns, wrapping around
+    /// the edges. Positive values move cells down and to the right.
+    pub fn shift(&mut self, d_row: i32, d_col: i32) {
+        self.record_edit();
+        let (width, height) = (self.width as i64, self.height as i64);
+        self.remap(self.width, self.height, |row, col| {
+            Some((
+                (row as i64 - d_row as i64).rem_euclid(height) as u32,
+                (col as i64 - d_col as i64).rem_euclid(width) as u32,
+            ))
+        });
+    }
+
+    /// Rotates the grid a quarter turn clockwise, swapping width and height.
+    pub fn rotate_cw(&mut self) {
+        self.record_edit();
+        let last_row = self.height - 1;
+        self.remap(self.height, self.width, |row, col| {
+            Some((last_row - col, row))
+        });
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise, swapping width and height.
+    pub fn rotate_ccw(&mut self) {
+        self.record_edit();
+        let last_col = self.width - 1;
+        self.remap(self.height, self.width, |row, col| {
+            Some((col, last_col - row))
+        });
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&mut self) {
+        self.record_edit();
+        let last_col = self.width - 1;
+        self.remap(self.width, self.height, |row, col| {
+            Some((row, last_col - col))
+        });
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&mut self) {
+        self.record_edit();
+        let last_row = self.height - 1;
+        self.remap(self.width, self.height, |row, col| {
+            Some((last_row - row, col))
+        });
+    }
+
+    /// Shrinks the universe to the bounding box of its live cells plus `margin`
+    /// dead cells on every side.
+    pub fn crop_to_content(&mut self, margin: u32) -> Result<(), Error> {
+        let (min_row, min_col, max_row, max_col) = self.live_bounds().ok_or(Error::NoLiveCells)?;
+        let padded = |span: u32| margin.checked_mul(2)?.checked_add(span)?.checked_add(1);
+        let (width, height) = match (padded(max_col - min_col), padded(max_row - min_row)) {
+            (Some(width), Some(height)) => (width, height),
+            (width, height) => {
+                return Err(Error::InvalidSize {
+                    width: width.unwrap_or(u32::MAX),
+                    height: height.unwrap_or(u32::MAX),
+                })
+            }
+        };
+        check_size(width, height)?;
+        self.record_edit();
+
+        let (old_width, old_height) = (self.width, self.height);
+        self.remap(width, height, |row, col| {
+            let r = row.checked_add(min_row)?.checked_sub(margin)?;
+            let c = col.checked_add(min_col)?.checked_sub(margin)?;
+            if r < old_height && c < old_width {
+                Some((r, c))
+            } else {
+                None
+            }
+        });
+        Ok(())
+    }
+
+    /// Changes the grid size while keeping existing cells, cropping or padding
+    /// with dead cells around the `anchor`.
+    pub fn resize(&mut self, width: u32, height: u32, anchor: Anchor) -> Result<(), Error> {
+        check_size(width, height)?;
+        self.record_edit();
+
+        let (old_width, old_height) = (self.width as i64, self.height as i64);
+        let (row_offset, col_offset) = anchor.offsets((self.height, self.width), (height, width));
+        self.remap(width, height, |row, col| {
+            let r = row as i64 - row_offset;
+            let c = col as i64 - col_offset;
+            if r >= 0 && c >= 0 && r < old_height && c < old_width {
+                Some((r as u32, c as u32))
+            } else {
+                None
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use wasm_bindgen::prelude::*;
+
+use crate::draw::line_points;
+use crate::engine::{Engine, MOORE};
+use crate::{Cell, Universe};
+
+/// Cell states of Wireworld, as read from `state()` and `states()`.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WireState {
+    Empty = 0,
+    /// Front of an electron; becomes a tail.
+    Head = 1,
+    /// Back of an electron; turns back into wire.
+    Tail = 2,
+    /// Wire, which becomes a head next to one or two heads.
+    Conductor = 3,
+}
+
+impl Universe {
+    // The next Wireworld state of a cell
+    pub(crate) fn wireworld_state(&self, row: u32, col: u32, state: u8) -> u8 {
+        match state {
+            1 => WireState::Tail as u8,
+            2 => WireState::Conductor as u8,
+            3 => {
+                let heads = MOORE
+                    .iter()
+                    .filter(|&&offset| self.neighbor_state(row, col, offset) == 1)
+                    .count();
+                if heads == 1 || heads == 2 {
+                    WireState::Head as u8
+                } else {
+                    WireState::Conductor as u8
+                }
+            }
+            _ => WireState::Empty as u8,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Switches to Wireworld, whose four states are listed in `WireState`. Cells that
+    /// are already alive become wire.
+    pub fn set_wireworld(&mut self) {
+        self.sync_states();
+        for state in self.states.iter_mut().filter(|state| **state != 0) {
+            *state = WireState::Conductor as u8;
+        }
+        self.engine = Engine::Wireworld;
+    }
+
+    /// Paints a line of `state`, typically `Conductor` or `Empty`, from (`r0`, `c0`)
+    /// to (`r1`, `c1`). Parts outside the grid are skipped.
+    pub fn draw_wire(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, state: WireState) {
+        self.record_edit();
+        self.sync_states();
+        for (row, col) in line_points((r0, c0), (r1, c1), self.height, self.width) {
+            let idx = self.get_index(row, col);
+            self.states[idx] = state as u8;
+            self.cells[idx] = if state == WireState::Empty {
+                Cell::Dead
+            } else {
+                Cell::Alive
+            };
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::iter;
+use wasm_bindgen::prelude::*;
+
+use crate::rule::LifeRule;
+use crate::{Cell, Error, Universe};
+
+// A rectangle of the grid running its own birth/survival rule
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Zone {
+    row: u32,
+    col: u32,
+    height: u32,
+    width: u32,
+    rule: LifeRule,
+}
+
+impl Zone {
+    fn contains(&self, row: u32, col: u32) -> bool {
+        row >= self.row
+            && row - self.row < self.height
+            && col >= self.col
+            && col - self.col < self.width
+    }
+}
+
+impl Universe {
+    // The zone whose rule the cell at `row`, `col` follows, the latest covering it
+    fn zone_at(&self, row: u32, col: u32) -> Option<&Zone> {
+        self.zones.iter().rev().find(|zone| zone.contains(row, col))
+    }
+
+    // Which rule the cell at `row`, `col` follows: 0 for the universe's rule, or
+    // one more than the index of its zone
+    pub(crate) fn zone_index(&self, row: u32, col: u32) -> usize {
+        self.zones
+            .iter()
+            .rposition(|zone| zone.contains(row, col))
+            .map_or(0, |idx| idx + 1)
+    }
+
+    // Lookup tables for `rule` and then each zone, to index by `zone_index()`
+    pub(crate) fn zone_lookups(&self, rule: LifeRule) -> Vec<[[Cell; 10]; 2]> {
+        let rules = iter::once(rule).chain(self.zones.iter().map(|zone| zone.rule));
+        rules.map(LifeRule::lookup).collect()
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Runs the birth/survival `rule` inside a `height` x `width` rectangle whose
+    /// top-left corner is at `row`, `col`, while the rest of the grid keeps the
+    /// universe's rule. Each cell follows the zone it sits in, and neighbors are
+    /// counted across zone borders, so patterns spill from one zone into the next.
+    /// Where zones overlap, the one added last wins. Only applies while a plain
+    /// Life-like rule is in effect.
+    pub fn add_rule_zone(
+        &mut self,
+        row: u32,
+        col: u32,
+        height: u32,
+        width: u32,
+        rule: &str,
+    ) -> Result<(), Error> {
+        let rule = LifeRule::parse(rule)?;
+        self.zones.push(Zone {
+            row,
+            col,
+            height,
+            width,
+            rule,
+        });
+        Ok(())
+    }
+
+    /// Removes every rule zone, returning the whole grid to the universe's rule.
+    pub fn clear_rule_zones(&mut self) {
+        self.zones.clear();
+    }
+
+    pub fn rule_zone_count(&self) -> usize {
+        self.zones.len()
+    }
+
+    /// The rule the cell at `row`, `col` follows, in `B../S..` notation.
+    pub fn rule_at(&self, row: u32, col: u32) -> String {
+        match self.zone_at(row, col) {
+            Some(zone) => zone.rule.to_string(),
+            None => self.rule(),
+        }
+    }
+}
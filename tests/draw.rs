@@ -0,0 +1,178 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{BlendMode, Cell, Universe};
+
+fn grid(text: &str) -> Vec<Cell> {
+    Universe::from_text(text).unwrap().get_cells().to_vec()
+}
+
+#[test]
+fn draw_line_is_continuous() {
+    let mut universe = Universe::new_empty(6, 4).unwrap();
+    universe.draw_line(0, 0, 3, 5, Cell::Alive);
+    assert_eq!(
+        universe.get_cells(),
+        &grid(
+            "\
+O.....
+.OO...
+...OO.
+.....O"
+        )[..]
+    );
+
+    universe.draw_line(3, 5, 0, 0, Cell::Dead);
+    assert!(universe.get_cells().iter().all(|&c| c == Cell::Dead));
+
+    // Off-grid endpoints are clipped
+    universe.draw_line(1, 0, 1, 100, Cell::Alive);
+    assert_eq!(
+        universe.get_cells(),
+        &grid("......\nOOOOOO\n......\n......")[..]
+    );
+}
+
+#[test]
+fn rectangles_outline_and_fill() {
+    let mut universe = Universe::new_empty(6, 5).unwrap();
+    universe.draw_rect(0, 1, 4, 4, Cell::Alive);
+    assert_eq!(
+        universe.get_cells(),
+        &grid(
+            "\
+.OOOO.
+.O..O.
+.O..O.
+.OOOO.
+......"
+        )[..]
+    );
+
+    universe.fill_rect(1, 2, 10, 10, Cell::Alive);
+    assert_eq!(
+        universe.get_cells(),
+        &grid(
+            "\
+.OOOO.
+.OOOOO
+.OOOOO
+.OOOOO
+..OOOO"
+        )[..]
+    );
+}
+
+#[test]
+fn circles_outline_and_fill() {
+    let mut universe = Universe::new_empty(7, 7).unwrap();
+    universe.draw_circle(3, 3, 3, Cell::Alive, false);
+    assert_eq!(
+        universe.get_cells(),
+        &grid(
+            "\
+..OOO..
+.O...O.
+O.....O
+O.....O
+O.....O
+.O...O.
+..OOO.."
+        )[..]
+    );
+
+    universe.clear();
+    universe.draw_circle(3, 3, 2, Cell::Alive, true);
+    assert_eq!(
+        universe.get_cells(),
+        &grid(
+            "\
+.......
+..OOO..
+.OOOOO.
+.OOOOO.
+.OOOOO.
+..OOO..
+......."
+        )[..]
+    );
+}
+
+#[test]
+fn clear_region_erases_rectangle() {
+    let mut universe = Universe::from_text("OOOO\nOOOO\nOOOO").unwrap();
+    universe.clear_region(1, 1, 5, 2);
+    assert_eq!(universe.get_cells(), &grid("OOOO\nO..O\nO..O")[..]);
+}
+
+#[test]
+fn invert_region_flips_cells() {
+    let mut universe = Universe::from_text("O..O\n.OO.\nO..O").unwrap();
+    universe.invert_region(0, 1, 2, 9);
+    assert_eq!(universe.get_cells(), &grid("OOO.\n...O\nO..O")[..]);
+}
+
+#[test]
+fn copy_region_extracts_subgrid() {
+    let universe = Universe::from_text("O...\n.O.O\n..O.").unwrap();
+    let copy = universe.copy_region(1, 1, 5, 5).unwrap();
+    assert_eq!((copy.width(), copy.height()), (3, 2));
+    assert_eq!(copy.get_cells(), &grid("O.O\n.O.")[..]);
+    assert!(universe.copy_region(3, 0, 1, 1).is_err());
+}
+
+#[test]
+fn paste_blends_and_wraps() {
+    let piece = Universe::from_text("OO\n.O").unwrap();
+    let base = || Universe::from_text("O...\n....\n...O").unwrap();
+
+    let mut or = base();
+    or.paste(&piece, 2, 3, BlendMode::Or);
+    assert_eq!(or.get_cells(), &grid("O...\n....\nO..O")[..]);
+
+    let mut xor = base();
+    xor.paste(&piece, 2, 3, BlendMode::Xor);
+    assert_eq!(xor.get_cells(), &grid("....\n....\nO...")[..]);
+
+    let mut copy = base();
+    copy.paste(&piece, 0, 0, BlendMode::Copy);
+    assert_eq!(copy.get_cells(), &grid("OO..\n.O..\n...O")[..]);
+
+    let mut and = base();
+    and.paste(&piece, 0, 0, BlendMode::And);
+    assert_eq!(and.get_cells(), &grid("O...\n....\n...O")[..]);
+}
+
+#[test]
+fn paste_rle_uses_blend_modes() {
+    let base = || Universe::from_text("O...\n....\n...O").unwrap();
+
+    let mut or = base();
+    or.paste_rle("x = 2, y = 2\n2o$bo!", 2, 3, BlendMode::Or)
+        .unwrap();
+    assert_eq!(or.get_cells(), &grid("O...\n....\nO..O")[..]);
+
+    // Copy clears the dead cells of the bounding box too
+    let mut copy = base();
+    copy.paste_rle("x = 3, y = 1\nbo!", 0, 0, BlendMode::Copy)
+        .unwrap();
+    assert_eq!(copy.get_cells(), &grid(".O..\n....\n...O")[..]);
+
+    assert!(base()
+        .paste_rle("x = 5, y = 1\n5o!", 0, 0, BlendMode::Or)
+        .is_err());
+}
+
+#[test]
+fn huge_shapes_are_clipped_to_the_grid() {
+    let mut universe = Universe::new_empty(6, 4).unwrap();
+    universe.draw_circle(2, 3, 1_000_000_000, Cell::Alive, true);
+    assert!(universe.get_cells().iter().all(|&c| c == Cell::Alive));
+
+    universe.draw_circle(2, 3, u32::MAX, Cell::Dead, false);
+    universe.draw_rect(1, 1, u32::MAX, u32::MAX, Cell::Dead);
+    universe.draw_line(u32::MAX, 0, 0, u32::MAX, Cell::Dead);
+    universe.draw_line(4_000_000_000, 3, 0, 3, Cell::Dead);
+    assert_eq!(
+        universe.get_cells(),
+        &grid("OOO.OO\nO.....\nO.O.OO\nO.O.OO")[..]
+    );
+}
@@ -0,0 +1,438 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Boundary, Error, Neighborhood, SparseUniverse, Universe};
+
+const GLIDER_RLE: &str = "#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+
+#[test]
+fn from_rle_centers_pattern() {
+    let universe = Universe::from_rle(GLIDER_RLE, 7, 5).unwrap();
+    let expected = Universe::from_text(
+        "\
+.......
+...O...
+....O..
+..OOO..
+.......",
+    )
+    .unwrap();
+    assert_eq!(universe.get_cells(), expected.get_cells());
+}
+
+#[test]
+fn from_rle_reports_errors() {
+    assert_eq!(
+        Universe::from_rle(GLIDER_RLE, 2, 2).err(),
+        Some(Error::PatternTooLarge {
+            width: 3,
+            height: 3
+        })
+    );
+    assert!(matches!(
+        Universe::from_rle("x = 3, y = 3\nbo?o!", 5, 5),
+        Err(Error::Parse { line: 2, .. })
+    ));
+
+    // Runs are checked against the header before any cells are stored
+    for rle in [
+        "4294967295bo!",
+        "x = 3, y = 1\n1000000000o!",
+        "x = 3, y = 1\no$o!",
+    ] {
+        assert!(matches!(
+            Universe::from_rle(rle, 5, 5),
+            Err(Error::Parse { .. })
+        ));
+    }
+}
+
+#[test]
+fn load_rle_stamps_into_existing_grid() {
+    let mut universe = Universe::from_text("O...\n....\n....\n....").unwrap();
+    universe.load_rle(GLIDER_RLE, 2, 2).unwrap();
+    let expected = Universe::from_text("O.OO\n....\n...O\nO...").unwrap();
+    // Rows and columns past the edge wrap around
+    assert_eq!(universe.get_cells(), expected.get_cells());
+
+    assert!(matches!(
+        universe.load_rle("x = 2, y = 1\n2o$3", 0, 0),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(universe.undo());
+    assert!(!universe.can_undo());
+}
+
+#[test]
+fn to_rle_round_trips() {
+    let universe = Universe::from_rle(GLIDER_RLE, 5, 4).unwrap();
+    assert_eq!(
+        universe.to_rle(),
+        "x = 5, y = 4, rule = B3/S23\n2bo$3bo$b3o!\n"
+    );
+    assert_eq!(
+        universe.region_to_rle(0, 1, 3, 10),
+        "x = 4, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+    );
+
+    // Long rows wrap at 70 characters without splitting a run
+    let mut wide = Universe::new_empty(200, 3).unwrap();
+    for col in (0..200).step_by(2) {
+        wide.set_alive(0, col).unwrap();
+    }
+    wide.set_alive(2, 0).unwrap();
+    let rle = wide.to_rle();
+    assert!(rle.lines().all(|line| line.len() <= 70));
+    let copy = Universe::from_rle(&rle, 200, 3).unwrap();
+    assert_eq!(copy.get_cells(), wide.get_cells());
+}
+
+#[test]
+fn plaintext_round_trips() {
+    let text = "!Name: Glider\n!A comment\n.O.\n\nOOO\n";
+    let universe = Universe::from_plaintext(text, 3, 3).unwrap();
+    assert_eq!(universe.to_plaintext(), ".O.\n...\nOOO\n");
+
+    let mut target = Universe::new_empty(4, 4).unwrap();
+    target
+        .load_plaintext(&universe.to_plaintext(), 1, 1)
+        .unwrap();
+    assert_eq!(target.live_cells(), vec![1, 2, 3, 1, 3, 2, 3, 3]);
+
+    assert_eq!(
+        Universe::from_plaintext("!x\n.O\nO*", 3, 3).err(),
+        Some(Error::Parse {
+            line: 3,
+            message: "unexpected character '*'".to_string()
+        })
+    );
+}
+
+#[test]
+fn life106_round_trips() {
+    let text = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+    let universe = Universe::from_life106(text, 3, 3).unwrap();
+    assert_eq!(
+        universe.to_life106(),
+        "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n"
+    );
+
+    let sparse = SparseUniverse::from_life106(text).unwrap();
+    assert!(sparse.is_alive(-1, 0));
+    assert_eq!(
+        SparseUniverse::from_life106(&sparse.to_life106())
+            .unwrap()
+            .live_cells(),
+        sparse.live_cells()
+    );
+
+    assert!(matches!(
+        Universe::from_life106("#Life 1.06\n1 2 3", 5, 5),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert_eq!(
+        Universe::from_life106("#Life 1.06\n-2147483648 0\n2147483647 0\n", 5, 5).err(),
+        Some(Error::PatternTooLarge {
+            width: u32::MAX,
+            height: 1
+        })
+    );
+}
+
+#[test]
+fn life105_reads_offset_blocks() {
+    let text = "#Life 1.05\n#D Two blocks\n#N\n#P -1 -1\n.*\n*.\n#P 2 0\n**\n";
+    let sparse = SparseUniverse::from_life105(text).unwrap();
+    assert_eq!(sparse.live_cells(), vec![-1, 0, 0, -1, 0, 2, 0, 3]);
+
+    let universe = Universe::from_life105(text, 5, 2).unwrap();
+    assert_eq!(universe.live_cells(), vec![0, 1, 1, 0, 1, 3, 1, 4]);
+
+    assert!(matches!(
+        Universe::from_life105("#Life 1.05\n#P 0\n*", 5, 5),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        Universe::from_life105("#R 23-3\n*", 5, 5),
+        Err(Error::Parse { line: 1, .. })
+    ));
+    assert!(matches!(
+        Universe::from_life105("#P 2147483647 2147483647\n**", 5, 5),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn macrocell_round_trips() {
+    // Golly's own output for a glider
+    let glider = "[M2] (golly 4.2)\n#R B3/S23\n.*$..*$***$\n4 0 0 0 1\n";
+    let universe = Universe::from_macrocell(glider, 3, 3).unwrap();
+    assert_eq!(universe.live_cells(), vec![0, 1, 1, 2, 2, 0, 2, 1, 2, 2]);
+    assert!(universe
+        .to_macrocell()
+        .ends_with("#R B3/S23\n.*$..*$***$\n"));
+
+    // Repeated blocks are written once
+    let mut wide = Universe::new_empty(40, 20).unwrap();
+    wide.insert_pattern("block", 1, 1).unwrap();
+    wide.insert_pattern("block", 1, 33).unwrap();
+    wide.insert_pattern("glider", 17, 17).unwrap();
+    let text = wide.to_macrocell();
+    assert_eq!(text.matches("$.**$.**$").count(), 1);
+    let mut copy = Universe::new_empty(40, 20).unwrap();
+    copy.load_macrocell(&text, 1, 1).unwrap();
+    assert_eq!(copy.get_cells(), wide.get_cells());
+
+    assert!(matches!(
+        Universe::from_macrocell("[M2]\n4 0 0 0 2\n", 8, 8),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        Universe::from_macrocell("[M2]\n1 0 1 1 0\n", 8, 8),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn macrocell_size_is_checked_before_expanding() {
+    // Every level repeats the one below four times, for 4^60 live cells
+    let mut text = String::from("[M2]\n*$\n");
+    for level in 4..=63 {
+        let child = level - 3;
+        text += &format!("{} {} {} {} {}\n", level, child, child, child, child);
+    }
+    assert_eq!(
+        Universe::from_macrocell(&text, 8, 8).err(),
+        Some(Error::PatternTooLarge {
+            width: u32::MAX,
+            height: u32::MAX
+        })
+    );
+
+    // A cell at the top and one exactly `u32::MAX` rows further down
+    let mut text = String::from("[M2]\n*$\n$$$$$$$*$\n");
+    let (mut top, mut bottom) = (1, 2);
+    for level in 4..32 {
+        text += &format!("{} {} 0 0 0\n{} 0 0 {} 0\n", level, top, level, bottom);
+        top = level * 2 - 5;
+        bottom = top + 1;
+    }
+    text += &format!("32 {} 0 {} 0\n", top, bottom);
+    assert_eq!(
+        Universe::from_macrocell(&text, 8, 8).err(),
+        Some(Error::PatternTooLarge {
+            width: 1,
+            height: u32::MAX
+        })
+    );
+}
+
+#[test]
+fn apgcodes_match_catagolue() {
+    let known = [
+        ("block", "xs4_33"),
+        ("beehive", "xs6_696"),
+        ("boat", "xs5_253"),
+        ("blinker", "xp2_7"),
+        ("toad", "xp2_7e"),
+        ("beacon", "xp2_318c"),
+        ("glider", "xq4_153"),
+        ("lwss", "xq4_6frc"),
+    ];
+    for &(name, code) in known.iter() {
+        let mut universe = Universe::new_empty(16, 16).unwrap();
+        universe.insert_pattern(name, 5, 4).unwrap();
+        assert_eq!(universe.apgcode().unwrap(), code, "{}", name);
+
+        let decoded = Universe::from_apgcode(code, 16, 16).unwrap();
+        assert_eq!(decoded.apgcode().unwrap(), code, "{}", name);
+    }
+
+    let mut empty = Universe::new_empty(4, 4).unwrap();
+    assert_eq!(empty.apgcode(), Err(Error::NoLiveCells));
+    empty.insert_pattern("r_pentomino", 1, 1).unwrap();
+    assert_eq!(empty.apgcode(), Err(Error::Aperiodic { generations: 1024 }));
+}
+
+#[test]
+fn binary_round_trips() {
+    let mut universe = Universe::new_with_seed(13, 7, 3).unwrap();
+    universe.set_boundary(Boundary::Fixed);
+    let bytes = universe.to_bytes();
+    // The header, "B3/S23" and the packed cells
+    assert_eq!(bytes.len(), 18 + 6 + 12);
+    let copy = Universe::from_bytes(&bytes).unwrap();
+    assert_eq!(copy.get_cells(), universe.get_cells());
+    assert_eq!(copy.boundary(), Boundary::Fixed);
+
+    universe.set_anchor(1, 1, true).unwrap();
+    universe.set_tag(2, 2, 5).unwrap();
+    let copy = Universe::from_bytes(&universe.to_bytes()).unwrap();
+    assert!(copy.is_anchor(1, 1));
+    assert_eq!(copy.tag(2, 2), 5);
+
+    // Rules and their decay states come along
+    universe.set_rule("/2/3").unwrap();
+    universe.tick();
+    universe.set_count_self(true);
+    let copy = Universe::from_bytes(&universe.to_bytes()).unwrap();
+    assert_eq!(copy.rule(), universe.rule());
+    let states = |universe: &Universe| -> Vec<u8> {
+        (0..universe.height())
+            .flat_map(|row| (0..universe.width()).map(move |col| universe.state(row, col)))
+            .collect()
+    };
+    assert!(states(&universe).contains(&2));
+    assert_eq!(states(&copy), states(&universe));
+    assert!(copy.counts_self());
+    universe.set_rule("B36/S23V").unwrap();
+    let copy = Universe::from_bytes(&universe.to_bytes()).unwrap();
+    assert_eq!(copy.rule(), "B36/S23V");
+
+    // Version 1 files had no rule
+    let mut v1 = b"GOL\x01".to_vec();
+    v1.extend_from_slice(&[2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0b10]);
+    let copy = Universe::from_bytes(&v1).unwrap();
+    assert_eq!(copy.rule(), "B3/S23");
+    assert_eq!(copy.live_cells(), vec![0, 1]);
+
+    let mut truncated = universe.to_bytes();
+    truncated.pop();
+    assert!(matches!(
+        Universe::from_bytes(&truncated),
+        Err(Error::InvalidData(_))
+    ));
+    assert!(matches!(
+        Universe::from_bytes(b"GOL\x09"),
+        Err(Error::InvalidData(_))
+    ));
+}
+
+#[test]
+fn share_strings_round_trip() {
+    // A lone glider is shorter as RLE
+    let mut sparse = Universe::new_empty(64, 64).unwrap();
+    sparse.insert_pattern("glider", 10, 20).unwrap();
+    let share = sparse.to_share_string();
+    assert!(share.starts_with('r'));
+    assert!(share
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    let copy = Universe::from_share_string(&share).unwrap();
+    assert_eq!(copy.get_cells(), sparse.get_cells());
+
+    // The RLE header keeps the rule
+    sparse.set_rule("B36/S23").unwrap();
+    let share = sparse.to_share_string();
+    assert!(share.starts_with('r'));
+    assert_eq!(
+        Universe::from_share_string(&share).unwrap().rule(),
+        "B36/S23"
+    );
+
+    // A dense soup, or anything with walls, falls back to the binary snapshot
+    let mut dense = Universe::new_with_seed(10, 10, 1).unwrap();
+    dense.set_wall(0, 0, true).unwrap();
+    let share = dense.to_share_string();
+    assert!(share.starts_with('b'));
+    let copy = Universe::from_share_string(&share).unwrap();
+    assert_eq!(copy.get_cells(), dense.get_cells());
+    assert!(copy.is_wall(0, 0));
+
+    assert!(matches!(
+        Universe::from_share_string("b*"),
+        Err(Error::InvalidData(_))
+    ));
+    assert!(Universe::from_share_string("").is_err());
+}
+
+#[test]
+fn load_any_detects_the_format() {
+    let glider = Universe::from_rle(GLIDER_RLE, 3, 3).unwrap();
+    let texts = [
+        glider.to_rle(),
+        glider.to_plaintext(),
+        glider.to_life106(),
+        glider.to_macrocell(),
+        "#Life 1.05\n#P 0 0\n.*.\n..*\n***\n".to_string(),
+        "!Name: Glider\n.O.\n..O\nOOO\n".to_string(),
+    ];
+    for text in texts.iter() {
+        let mut universe = Universe::new_empty(3, 3).unwrap();
+        universe.load_any(text, 0, 0).unwrap();
+        assert_eq!(universe.get_cells(), glider.get_cells(), "{}", text);
+    }
+}
+
+#[test]
+fn every_rule_with_a_rulestring_survives_a_binary_round_trip() {
+    let mut universe = Universe::new_with_seed(12, 10, 5).unwrap();
+    let check = |universe: &mut Universe| {
+        universe.randomize_states();
+        universe.tick();
+        let mut copy = Universe::from_bytes(&universe.to_bytes()).unwrap();
+        assert_eq!(copy.rule(), universe.rule());
+        assert_eq!(copy.neighborhood(), universe.neighborhood());
+        assert_eq!(copy.state_count(), universe.state_count());
+        universe.tick();
+        copy.tick();
+        assert_eq!(
+            copy.get_cells(),
+            universe.get_cells(),
+            "{}",
+            universe.rule()
+        );
+    };
+
+    for rule in [
+        "B36/S23V",
+        "B2-a/S12",
+        "B2/S34H",
+        "/2/3",
+        "R2,C0,M1,S3..6,B3..4,NM",
+        "ImmigrationV",
+        "QuadLife",
+        "WireWorld",
+    ] {
+        universe.set_rule(rule).unwrap();
+        check(&mut universe);
+    }
+    universe.set_rule("B36/S23H").unwrap();
+    universe.set_colors(3).unwrap();
+    check(&mut universe);
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    universe.set_cyclic_rule(4, 1).unwrap();
+    check(&mut universe);
+    universe.set_neighborhood(Neighborhood::Moore);
+    universe.set_forest_fire(0.0, 0.0);
+    check(&mut universe);
+    universe.set_forest_fire(0.01, 0.001);
+    assert_eq!(
+        Universe::from_bytes(&universe.to_bytes()).unwrap().rule(),
+        "ForestFire p=0.01 f=0.001"
+    );
+
+    // Elementary rules carry on from the newest row
+    let mut universe = Universe::new_empty(12, 10).unwrap();
+    universe.set_alive(0, 6).unwrap();
+    universe.set_rule("W30").unwrap();
+    universe.tick();
+    universe.tick();
+    let mut copy = Universe::from_bytes(&universe.to_bytes()).unwrap();
+    assert_eq!(copy.rule(), "W30");
+    universe.tick();
+    copy.tick();
+    assert_eq!(copy.get_cells(), universe.get_cells());
+
+    // Rules that are only named can't be rebuilt, and say so
+    universe.set_ant_rule("RL").unwrap();
+    assert_eq!(
+        Universe::from_bytes(&universe.to_bytes()).err(),
+        Some(Error::InvalidRule("RL".to_string()))
+    );
+    universe
+        .set_kernel(3, &[1, 1, 1, 1, 0, 1, 1, 1, 1], 3, 3, 2, 3)
+        .unwrap();
+    assert!(matches!(
+        Universe::from_bytes(&universe.to_bytes()),
+        Err(Error::InvalidRule(_))
+    ));
+}
@@ -0,0 +1,179 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Cell, Universe};
+
+#[test]
+fn undo_and_redo_edits() {
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    assert!(!universe.can_undo());
+
+    universe.toggle_cell(0, 0).unwrap();
+    universe.fill_rect(2, 2, 2, 2, Cell::Alive);
+    universe.rotate_cw();
+    let edited = universe.get_cells().to_vec();
+
+    assert!(universe.undo());
+    assert!(universe.undo());
+    assert_eq!(universe.live_cells(), vec![0, 0]);
+    assert!(universe.redo());
+    assert!(universe.redo());
+    assert_eq!(universe.get_cells(), &edited[..]);
+    assert!(!universe.redo());
+
+    // A new edit discards the redo stack
+    universe.undo();
+    universe.clear();
+    assert!(!universe.can_redo());
+
+    // Rejected edits are not recorded
+    universe.clear_history();
+    assert!(universe.set_alive(9, 9).is_err());
+    assert!(!universe.can_undo());
+}
+
+#[test]
+fn history_depth_and_groups() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.set_history_depth(2);
+    for col in 0..4 {
+        universe.set_alive(0, col).unwrap();
+    }
+    assert!(universe.undo() && universe.undo());
+    assert!(!universe.undo());
+    assert_eq!(universe.live_cells(), vec![0, 0, 0, 1]);
+
+    universe.begin_edit_group();
+    universe.draw_line(1, 0, 1, 3, Cell::Alive);
+    universe.draw_line(2, 0, 2, 3, Cell::Alive);
+    universe.end_edit_group();
+    assert!(universe.undo());
+    assert_eq!(universe.live_cells(), vec![0, 0, 0, 1]);
+
+    universe.set_history_depth(0);
+    universe.clear();
+    assert!(!universe.can_undo());
+}
+
+#[test]
+fn construction_and_ticks_are_not_edits() {
+    let mut universe = Universe::new_with_seed(8, 8, 1).unwrap();
+    universe.tick();
+    assert!(!universe.can_undo());
+}
+
+#[test]
+fn undo_across_ticks_and_resizes() {
+    let mut universe = Universe::new_with_seed(12, 8, 3).unwrap();
+    let start = universe.get_cells().to_vec();
+
+    universe.toggle_cell(1, 1).unwrap();
+    universe.tick();
+    let ticked = universe.get_cells().to_vec();
+    universe.rotate_cw();
+    universe.tick();
+    universe.tick();
+    let rotated = universe.get_cells().to_vec();
+
+    assert!(universe.undo());
+    assert_eq!((universe.width(), universe.height()), (12, 8));
+    assert_eq!(universe.get_cells(), &ticked[..]);
+
+    // Generations run after an undo are discarded by the next one
+    universe.tick();
+    let resumed = universe.get_cells().to_vec();
+    assert!(universe.undo());
+    assert_eq!(universe.get_cells(), &start[..]);
+
+    assert!(universe.redo());
+    assert!(universe.redo());
+    assert_eq!((universe.width(), universe.height()), (8, 12));
+    assert_eq!(universe.get_cells(), &rotated[..]);
+    assert!(universe.undo());
+    assert_eq!(universe.get_cells(), &resumed[..]);
+}
+
+#[test]
+fn logged_and_copied_edits_undo_alike() {
+    type State = (u32, Vec<Cell>, Vec<u8>, Vec<bool>);
+    fn state(universe: &Universe) -> State {
+        let (width, height) = (universe.width(), universe.height());
+        let coords: Vec<(u32, u32)> = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .collect();
+        (
+            width,
+            universe.get_cells().to_vec(),
+            coords.iter().map(|&(r, c)| universe.tag(r, c)).collect(),
+            coords
+                .iter()
+                .map(|&(r, c)| universe.is_wall(r, c))
+                .collect(),
+        )
+    }
+
+    for run in 0..40 {
+        let mut universe = Universe::new_with_seed(8, 6, run).unwrap();
+        universe.set_history_depth(1000);
+        let (mut undo, mut redo): (Vec<State>, Vec<State>) = (Vec::new(), Vec::new());
+        let mut grouped = None;
+        let mut seed = run;
+        let mut next = |n: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+
+        for _ in 0..600 {
+            let (row, col) = (
+                next(universe.height() as u64) as u32,
+                next(universe.width() as u64) as u32,
+            );
+            let op = next(12);
+            let edit = op < 7;
+            if edit && grouped != Some(true) {
+                undo.push(state(&universe));
+                redo.clear();
+                if grouped == Some(false) {
+                    grouped = Some(true);
+                }
+            }
+            match op {
+                0 | 1 => universe.toggle_cell(row, col).unwrap(),
+                2 => universe.set_wall(row, col, next(2) == 0).unwrap(),
+                3 => universe.set_tag(row, col, next(4) as u8).unwrap(),
+                4 => universe.apply_delta(&[row, col, row, col, 0, 0]).unwrap(),
+                5 => universe.fill_rect(row, col, 2, 3, Cell::Alive),
+                6 => universe.rotate_cw(),
+                7 | 8 => universe.tick(),
+                9 => {
+                    let current = state(&universe);
+                    assert_eq!(universe.undo(), !undo.is_empty());
+                    if let Some(previous) = undo.pop() {
+                        redo.push(current);
+                        assert!(state(&universe) == previous);
+                    }
+                    grouped = None;
+                    universe.end_edit_group();
+                }
+                10 => {
+                    let current = state(&universe);
+                    assert_eq!(universe.redo(), !redo.is_empty());
+                    if let Some(next) = redo.pop() {
+                        undo.push(current);
+                        assert!(state(&universe) == next);
+                    }
+                    grouped = None;
+                    universe.end_edit_group();
+                }
+                _ if grouped.is_some() => {
+                    universe.end_edit_group();
+                    grouped = None;
+                }
+                _ => {
+                    universe.begin_edit_group();
+                    grouped = Some(false);
+                }
+            }
+        }
+    }
+}
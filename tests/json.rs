@@ -0,0 +1,79 @@
+#![cfg(feature = "serde")]
+
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Boundary, Error, Universe, UniverseBuilder};
+
+#[test]
+fn json_round_trips() {
+    let mut universe = Universe::new_with_seed(12, 10, 7).unwrap();
+    universe.set_boundary(Boundary::Fixed);
+    universe.set_wall(0, 0, true).unwrap();
+    universe.set_tag(3, 4, 9).unwrap();
+
+    let mut copy = Universe::from_json(&universe.to_json()).unwrap();
+    assert_eq!(copy.get_cells(), universe.get_cells());
+    assert_eq!(copy.boundary(), Boundary::Fixed);
+    assert!(copy.is_wall(0, 0));
+    assert_eq!(copy.tag(3, 4), 9);
+    assert!(!copy.can_undo());
+
+    // The generator state comes along, so both continue identically
+    universe.randomize(0.5);
+    copy.randomize(0.5);
+    assert_eq!(copy.get_cells(), universe.get_cells());
+
+    // So do the rule, neighborhood and decay states
+    universe.set_rule("B2/S/C4V").unwrap();
+    universe.tick();
+    let copy = Universe::from_json(&universe.to_json()).unwrap();
+    assert_eq!(copy.rule(), universe.rule());
+    assert_eq!(copy.neighborhood(), universe.neighborhood());
+    let states = |universe: &Universe| -> Vec<u8> {
+        (0..universe.height())
+            .flat_map(|row| (0..universe.width()).map(move |col| universe.state(row, col)))
+            .collect()
+    };
+    assert!(states(&universe).contains(&2));
+    assert_eq!(states(&copy), states(&universe));
+}
+
+#[test]
+fn from_json_rejects_bad_input() {
+    let json = r#"{"width":2,"height":2,"cells":[0,1,1],"boundary":"Wrap","rng":{"XorShift":{"state":1}}}"#;
+    assert!(matches!(
+        Universe::from_json(json),
+        Err(Error::Parse { .. })
+    ));
+    assert!(Universe::from_json("{").is_err());
+
+    let builder: UniverseBuilder =
+        serde_json::from_str(&serde_json::to_string(&UniverseBuilder::new().size(8, 4)).unwrap())
+            .unwrap();
+    assert_eq!(builder.build().unwrap().width(), 8);
+}
+
+#[test]
+fn json_keeps_multi_state_rules() {
+    let mut universe = Universe::new_with_seed(10, 8, 2).unwrap();
+    universe.set_cyclic_rule(5, 2).unwrap();
+    universe.randomize_states();
+    for setup in [
+        |_: &mut Universe| {},
+        |u: &mut Universe| u.set_wireworld(),
+        |u: &mut Universe| u.set_forest_fire(0.25, 0.5),
+        |u: &mut Universe| u.set_colors(4).unwrap(),
+    ] {
+        setup(&mut universe);
+        let copy = Universe::from_json(&universe.to_json()).unwrap();
+        assert_eq!(copy.rule(), universe.rule());
+        assert_eq!(copy.state_count(), universe.state_count());
+    }
+
+    universe
+        .set_rule_table("n_states:3\nneighborhood:Moore\nsymmetries:none\n")
+        .unwrap();
+    assert!(matches!(
+        Universe::from_json(&universe.to_json()),
+        Err(Error::InvalidRule(_))
+    ));
+}
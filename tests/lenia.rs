@@ -0,0 +1,31 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::Lenia;
+
+#[test]
+fn growth_follows_the_kernel_average() {
+    let mut lenia = Lenia::new(32, 32).unwrap();
+
+    // On a uniform field the kernel average equals the value itself, so a field at
+    // the growth peak grows by the full step and an empty field stays empty
+    for row in 0..32 {
+        for col in 0..32 {
+            lenia.set_value(row, col, 0.15);
+        }
+    }
+    lenia.tick();
+    assert!((lenia.value(5, 5) - 0.25).abs() < 1e-4);
+    assert!((lenia.value(31, 0) - 0.25).abs() < 1e-4);
+
+    let mut empty = Lenia::new(16, 16).unwrap();
+    empty.set_radius(4);
+    empty.tick();
+    assert_eq!(empty.value(3, 3), 0.0);
+    assert_eq!(empty.generation(), 1);
+
+    let mut random = Lenia::new(8, 8).unwrap();
+    random.randomize(9);
+    let rgba = random.to_rgba();
+    assert_eq!(rgba.len(), 8 * 8 * 4);
+    assert_eq!(rgba[3], 255);
+    assert!(Lenia::new(0, 8).is_err());
+}
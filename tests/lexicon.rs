@@ -0,0 +1,31 @@
+#![cfg(feature = "lexicon")]
+
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{lexicon_description, lexicon_rle, list_patterns, pattern_names, Universe};
+
+#[test]
+fn lexicon_patterns_are_what_they_claim() {
+    let expected = [
+        ("ship", "xs6_"),
+        ("eater_1", "xs7_"),
+        ("clock", "xp2_"),
+        ("caterer", "xp3_"),
+        ("mold", "xp4_"),
+        ("octagon_2", "xp5_"),
+        ("koks_galaxy", "xp8_"),
+        ("tumbler", "xp14_"),
+        ("copperhead", "xq10_"),
+    ];
+    for &(name, prefix) in expected.iter() {
+        let mut universe = Universe::new_empty(24, 24).unwrap();
+        universe.insert_pattern(name, 6, 6).unwrap();
+        assert!(universe.apgcode().unwrap().starts_with(prefix), "{}", name);
+    }
+
+    for name in list_patterns() {
+        assert!(pattern_names().contains(&name));
+        assert!(lexicon_description(&name).is_some());
+        assert!(Universe::from_rle(&lexicon_rle(&name).unwrap(), 16, 16).is_ok());
+    }
+    assert_eq!(lexicon_rle("nonexistent"), None);
+}
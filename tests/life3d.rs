@@ -0,0 +1,35 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::Life3d;
+
+#[test]
+fn layers_count_neighbors_in_three_dimensions() {
+    let mut life = Life3d::new(8, 8, 4).unwrap();
+    assert_eq!(life.rule(), "4555");
+
+    // Five cells in one layer give birth to the cells touching all of them in the
+    // layers above and below
+    for &(row, col) in &[(3, 3), (3, 4), (4, 3), (4, 4), (3, 5)] {
+        life.set_cell(1, row, col, true);
+    }
+    life.tick();
+    assert!(life.is_alive(0, 4, 4) && life.is_alive(2, 4, 4));
+    assert_eq!(life.generation(), 1);
+
+    let slice = life.slice(2);
+    assert_eq!(slice.len(), 64);
+    assert_eq!(slice[4 * 8 + 4], 1);
+    assert!(life.slice(4).is_empty());
+
+    life.set_rule("5766").unwrap();
+    assert_eq!(life.rule(), "5766");
+    life.set_rule("4,12,13,26").unwrap();
+    assert_eq!(life.rule(), "4,12,13,26");
+    assert!(life.set_rule("45").is_err());
+    assert!(life.set_rule("4,5,5,27").is_err());
+
+    life.randomize(3, 0.3);
+    assert!(life.population() > 0);
+    life.clear();
+    assert_eq!(life.population(), 0);
+    assert!(Life3d::new(8, 8, 0).is_err());
+}
@@ -0,0 +1,36 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Cell, PackedUniverse, Universe};
+
+#[test]
+fn packed_ticks_match_the_byte_grid() {
+    // Widths on, below and past a word boundary all wrap like the byte grid
+    for &(width, height) in &[(64, 16), (70, 20), (130, 9), (5, 5)] {
+        let mut universe = Universe::new_with_seed(width, height, 7).unwrap();
+        universe.set_rule("B36/S23").unwrap();
+        let mut packed = PackedUniverse::from_universe(&universe);
+        assert_eq!(packed.rule(), "B36/S23");
+        for _ in 0..30 {
+            universe.tick();
+            packed.tick();
+        }
+        let cells: Vec<u8> = universe
+            .get_cells()
+            .iter()
+            .map(|&cell| cell as u8)
+            .collect();
+        assert_eq!(packed.to_cells(), cells, "{}x{}", width, height);
+        assert_eq!(
+            packed.population() as usize,
+            universe.live_cells().len() / 2
+        );
+    }
+
+    let mut packed = PackedUniverse::new(100, 3).unwrap();
+    assert_eq!(packed.words_per_row(), 2);
+    packed.set_cell(1, 99, Cell::Alive);
+    assert!(packed.is_alive(1, 99));
+    packed.set_cell(1, 99, Cell::Dead);
+    assert_eq!(packed.population(), 0);
+    assert!(packed.set_rule("B3/S2x").is_err());
+    assert!(PackedUniverse::new(0, 3).is_err());
+}
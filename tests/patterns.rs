@@ -0,0 +1,208 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{
+    pattern_names, register_pattern, unregister_pattern, Boundary, Cell, Direction, Error,
+    Orientation, Universe,
+};
+
+fn grid(text: &str) -> Vec<Cell> {
+    Universe::from_text(text).unwrap().get_cells().to_vec()
+}
+
+#[test]
+fn insert_pattern_stamps_named_patterns() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.insert_pattern("beehive", 1, 2).unwrap();
+    assert_eq!(universe.get_cells(), &grid("....\nO..O\n.OO.\nO..O")[..]);
+    assert_eq!(
+        universe.insert_pattern("spaceship", 0, 0),
+        Err(Error::UnknownPattern("spaceship".to_string()))
+    );
+}
+
+#[test]
+fn oscillators_return_after_their_period() {
+    let periods = [
+        ("block", 1),
+        ("beehive", 1),
+        ("loaf", 1),
+        ("boat", 1),
+        ("tub", 1),
+        ("blinker", 2),
+        ("toad", 2),
+        ("beacon", 2),
+        ("pulsar", 3),
+        ("pentadecathlon", 15),
+    ];
+    for &(name, period) in periods.iter() {
+        assert!(pattern_names().contains(&name.to_string()));
+        let mut universe = Universe::new_empty(24, 24).unwrap();
+        universe.insert_pattern(name, 5, 5).unwrap();
+        let start = universe.get_cells().to_vec();
+        for generation in 1..=period {
+            universe.tick();
+            let same = universe.get_cells() == &start[..];
+            assert_eq!(same, generation == period, "{} at {}", name, generation);
+        }
+    }
+}
+
+#[test]
+fn glider_gun_fires_every_30_generations() {
+    let mut universe = Universe::new_empty(60, 40).unwrap();
+    universe.set_boundary(Boundary::Fixed);
+    universe.generate_glider_gun(1, 1);
+    assert_eq!(universe.live_cells().len(), 72);
+
+    let mut populations = Vec::new();
+    for _ in 0..60 {
+        universe.tick();
+        populations.push(universe.live_cells().len() / 2);
+    }
+    // The gun returns to its own shape plus one more glider (5 cells) each period
+    assert_eq!(populations[59], populations[29] + 5);
+}
+
+#[test]
+fn diehard_vanishes_after_130_generations() {
+    let mut universe = Universe::new_empty(64, 64).unwrap();
+    universe.insert_pattern("diehard", 30, 28).unwrap();
+    for _ in 0..129 {
+        universe.tick();
+    }
+    assert!(!universe.live_cells().is_empty());
+    universe.tick();
+    assert!(universe.live_cells().is_empty());
+}
+
+#[test]
+fn spaceships_travel_in_the_requested_direction() {
+    let headings = [
+        (Direction::Right, (0, 2)),
+        (Direction::Left, (0, -2)),
+        (Direction::Down, (2, 0)),
+        (Direction::Up, (-2, 0)),
+    ];
+    for name in ["lwss", "mwss", "hwss"].iter() {
+        for &(direction, (dr, dc)) in headings.iter() {
+            let mut universe = Universe::new_empty(20, 20).unwrap();
+            universe.insert_spaceship(name, 6, 6, direction).unwrap();
+            let mut expected = universe.clone();
+            expected.shift(dr, dc);
+            for _ in 0..4 {
+                universe.tick();
+            }
+            assert_eq!(
+                universe.get_cells(),
+                expected.get_cells(),
+                "{} {:?}",
+                name,
+                direction
+            );
+        }
+    }
+}
+
+#[test]
+fn oriented_insert_aims_a_glider_every_way() {
+    let orientations = [
+        Orientation::Identity,
+        Orientation::RotateCw,
+        Orientation::Rotate180,
+        Orientation::RotateCcw,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+        Orientation::Transpose,
+        Orientation::AntiTranspose,
+    ];
+    let mut shapes = Vec::new();
+    for &orientation in orientations.iter() {
+        let mut universe = Universe::new_empty(5, 5).unwrap();
+        universe
+            .insert_pattern_oriented("glider", 1, 1, orientation)
+            .unwrap();
+        // Every orientation stays inside the 3x3 box at (1, 1)
+        assert_eq!(universe.live_cells().len(), 10);
+        universe.clear_region(1, 1, 3, 3);
+        assert!(universe.live_cells().is_empty(), "{:?}", orientation);
+        universe.undo();
+        shapes.push(universe.get_cells().to_vec());
+    }
+
+    // A glider is chiral, so all eight placements differ
+    for i in 0..shapes.len() {
+        for j in i + 1..shapes.len() {
+            assert_ne!(shapes[i], shapes[j]);
+        }
+    }
+
+    let mut rotated = Universe::new_empty(5, 5).unwrap();
+    rotated
+        .insert_pattern_oriented("glider", 1, 1, Orientation::RotateCw)
+        .unwrap();
+    assert_eq!(
+        rotated.get_cells(),
+        &grid(".....\n.O...\n.O.O.\n.OO..\n.....")[..]
+    );
+}
+
+#[test]
+fn registered_patterns_work_like_builtins() {
+    register_pattern("test_domino", "x = 2, y = 1\n2o!").unwrap();
+    assert!(pattern_names().contains(&"test_domino".to_string()));
+
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe
+        .insert_pattern_oriented("test_domino", 1, 1, Orientation::RotateCw)
+        .unwrap();
+    assert_eq!(universe.live_cells(), vec![1, 1, 2, 1]);
+
+    assert!(register_pattern("test_bad", "2o$?!").is_err());
+    assert!(unregister_pattern("test_domino"));
+    assert!(!unregister_pattern("test_domino"));
+    assert!(universe.insert_pattern("test_domino", 0, 0).is_err());
+}
+
+#[test]
+fn find_pattern_locates_isolated_copies() {
+    let mut universe = Universe::new_empty(20, 12).unwrap();
+    universe.insert_pattern("glider", 1, 1).unwrap();
+    universe
+        .insert_pattern_oriented("glider", 6, 10, Orientation::Rotate180)
+        .unwrap();
+    universe.insert_pattern("block", 1, 15).unwrap();
+    // A glider touching a block is not an isolated glider
+    universe.insert_pattern("glider", 8, 1).unwrap();
+    universe.insert_pattern("block", 9, 4).unwrap();
+
+    assert_eq!(universe.find_pattern("glider", false).unwrap(), vec![1, 1]);
+    assert_eq!(
+        universe.find_pattern("glider", true).unwrap(),
+        vec![1, 1, 6, 10]
+    );
+    assert_eq!(
+        universe
+            .find_pattern("x = 2, y = 2\n2o$2o!", false)
+            .unwrap(),
+        vec![1, 15]
+    );
+    assert_eq!(universe.find_pattern("", false), Err(Error::NoLiveCells));
+}
+
+#[test]
+fn find_pattern_matches_every_phase() {
+    let mut universe = Universe::new_empty(24, 24).unwrap();
+    universe.set_boundary(Boundary::Fixed);
+    for &(row, col) in &[(1, 1), (1, 12), (12, 1), (12, 12)] {
+        universe.insert_pattern("glider", row, col).unwrap();
+    }
+    universe.insert_pattern("blinker", 20, 20).unwrap();
+    universe.tick();
+    assert_eq!(universe.find_pattern("glider", false).unwrap().len(), 8);
+    universe.tick();
+    assert_eq!(universe.find_pattern("glider", false).unwrap().len(), 8);
+    assert_eq!(universe.find_pattern("blinker", false).unwrap().len(), 2);
+
+    // Other rules only match the phase given
+    universe.set_rule("B36/S23").unwrap();
+    assert!(universe.find_pattern("glider", false).unwrap().is_empty());
+}
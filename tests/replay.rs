@@ -0,0 +1,106 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Anchor, Error, Replay, Universe};
+
+#[test]
+fn record_and_export_replay() {
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    assert_eq!(universe.export_replay(), Err(Error::NoRecording));
+
+    universe.set_alive(2, 1).unwrap();
+    universe.set_alive(2, 2).unwrap();
+    universe.set_alive(2, 3).unwrap();
+    universe.start_recording();
+    assert!(universe.is_recording());
+    universe.tick();
+    universe.tick();
+    universe.stop_recording();
+    universe.tick();
+    assert!(!universe.is_recording());
+    assert_eq!(universe.recorded_frames(), 2);
+
+    let snapshot = {
+        let mut start = Universe::new_empty(5, 5).unwrap();
+        for col in 1..4 {
+            start.set_alive(2, col).unwrap();
+        }
+        start.to_bytes()
+    };
+    let mut expected = b"GLR\x01".to_vec();
+    expected.extend_from_slice(&(snapshot.len() as u32).to_le_bytes());
+    expected.extend_from_slice(&snapshot);
+    expected.extend_from_slice(&2u32.to_le_bytes());
+    // Blinker: cells 7, 11, 13 and 17 flip each generation
+    expected.extend_from_slice(&[4, 7, 4, 2, 4]);
+    expected.extend_from_slice(&[4, 7, 4, 2, 4]);
+    assert_eq!(universe.export_replay().unwrap(), expected);
+
+    // Resizing ends the recording
+    universe.start_recording();
+    universe.resize(6, 6, Anchor::TopLeft).unwrap();
+    universe.tick();
+    assert!(!universe.is_recording());
+    assert_eq!(universe.recorded_frames(), 0);
+}
+
+#[test]
+fn play_replay_forwards_and_back() {
+    let mut recorded = Universe::new_empty(8, 8).unwrap();
+    recorded.set_rule("B36/S23").unwrap();
+    recorded.insert_pattern("glider", 1, 1).unwrap();
+    let start = recorded.get_cells().to_vec();
+    recorded.start_recording();
+    let mut generations = vec![start.clone()];
+    for _ in 0..4 {
+        recorded.tick();
+        generations.push(recorded.get_cells().to_vec());
+    }
+    let replay = Replay::from_bytes(&recorded.export_replay().unwrap()).unwrap();
+    assert_eq!(replay.frame_count(), 4);
+
+    let mut viewer = Universe::new_empty(3, 3).unwrap();
+    viewer.play_replay(&replay).unwrap();
+    assert_eq!(viewer.width(), 8);
+    assert_eq!(viewer.rule(), "B36/S23");
+    assert_eq!(viewer.get_cells(), &start[..]);
+    assert!(!viewer.step_replay_back());
+    for generation in generations.iter().skip(1) {
+        assert!(viewer.step_replay());
+        assert_eq!(viewer.get_cells(), &generation[..]);
+    }
+    assert!(!viewer.step_replay());
+    assert_eq!(viewer.replay_position(), 4);
+
+    assert!(viewer.step_replay_back());
+    assert!(viewer.step_replay_back());
+    assert_eq!(viewer.get_cells(), &generations[2][..]);
+
+    let bytes = recorded.export_replay().unwrap();
+    assert!(Replay::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Replay::from_bytes(b"GOL\x01").is_err());
+}
+
+#[test]
+fn replays_keep_multi_state_rules() {
+    let mut recorded = Universe::new_with_seed(10, 10, 4).unwrap();
+    recorded.set_wireworld();
+    recorded.start_recording();
+    recorded.tick();
+    let replay = Replay::from_bytes(&recorded.export_replay().unwrap()).unwrap();
+
+    let mut viewer = Universe::new_empty(3, 3).unwrap();
+    viewer.play_replay(&replay).unwrap();
+    assert_eq!(viewer.rule(), "WireWorld");
+    assert!(viewer.step_replay());
+    viewer.tick();
+    recorded.tick();
+    assert_eq!(viewer.get_cells(), recorded.get_cells());
+
+    // A replay of a rule that can't be rebuilt is refused rather than replayed wrongly
+    recorded.set_ant_rule("RL").unwrap();
+    recorded.start_recording();
+    recorded.tick();
+    assert!(matches!(
+        Replay::from_bytes(&recorded.export_replay().unwrap()),
+        Err(Error::InvalidRule(_))
+    ));
+}
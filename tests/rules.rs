@@ -0,0 +1,790 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{
+    Anchor, Boundary, Cell, Conway, Direction, Error, NeighborInfo, Neighborhood, Rule, RulePreset,
+    Team, Universe, WireState,
+};
+
+const WIREWORLD: &str = "@RULE WireWorld
+# Electron heads become tails, tails become wire,
+# and wire with one or two heads next to it becomes a head
+
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={0,1,2,3}
+var c={0,1,2,3}
+var d={0,1,2,3}
+var e={0,1,2,3}
+var f={0,1,2,3}
+var g={0,1,2,3}
+var h={0,1,2,3}
+var i={0,2,3}
+var j={0,2,3}
+var k={0,2,3}
+var l={0,2,3}
+var m={0,2,3}
+var n={0,2,3}
+var o={0,2,3}
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+
+@COLORS
+1 0 128 255
+";
+
+#[test]
+fn rule_table_runs_wireworld() {
+    let mut universe = Universe::new_empty(8, 3).unwrap();
+    universe.set_rule_table(WIREWORLD).unwrap();
+    assert_eq!(universe.state_count(), 4);
+    for col in 0..8 {
+        universe.set_state(1, col, 3).unwrap();
+    }
+    universe.set_state(1, 1, 2).unwrap();
+    universe.set_state(1, 2, 1).unwrap();
+    assert_eq!(
+        universe.set_state(0, 0, 4),
+        Err(Error::InvalidState {
+            state: 4,
+            states: 4
+        })
+    );
+
+    universe.tick();
+    let wire: Vec<u8> = (0..8).map(|col| universe.state(1, col)).collect();
+    assert_eq!(wire, vec![3, 3, 2, 1, 3, 3, 3, 3]);
+    universe.tick();
+    let wire: Vec<u8> = (0..8).map(|col| universe.state(1, col)).collect();
+    assert_eq!(wire, vec![3, 3, 3, 2, 1, 3, 3, 3]);
+    assert_eq!(universe.get_cell(0, 0), Ok(Cell::Dead));
+    assert_eq!(universe.get_cell(1, 0), Ok(Cell::Alive));
+}
+
+#[test]
+fn rule_table_compact_lines_and_symmetry() {
+    // A dead cell with exactly one live orthogonal neighbor is born
+    let table = "n_states:2\nneighborhood:vonNeumann\nsymmetries:rotate4\n010001\n";
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    universe.set_rule_table(table).unwrap();
+    universe.set_alive(2, 2).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![1, 2, 2, 1, 2, 2, 2, 3, 3, 2]);
+
+    let bad = "n_states:2\nneighborhood:vonNeumann\n0100\n";
+    assert_eq!(
+        Universe::new_empty(2, 2).unwrap().set_rule_table(bad),
+        Err(Error::Parse {
+            line: 3,
+            message: "expected 6 entries but got 4".to_string()
+        })
+    );
+}
+
+#[test]
+fn birth_survival_rules() {
+    let mut universe = Universe::new_empty(6, 6).unwrap();
+    assert_eq!(universe.rule(), "B3/S23");
+    universe.set_rule("b2/s").unwrap();
+    assert_eq!(universe.rule(), "B2/S");
+    universe.set_alive(2, 2).unwrap();
+    universe.set_alive(2, 3).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![1, 2, 1, 3, 3, 2, 3, 3]);
+
+    universe.set_rule("23/36").unwrap();
+    assert_eq!(universe.rule(), "B36/S23");
+    universe.set_rule("S23B3").unwrap();
+    assert_eq!(universe.rule(), "B3/S23");
+    assert_eq!(
+        universe.set_rule("B9/S23"),
+        Err(Error::InvalidRule("B9/S23".to_string()))
+    );
+    assert!(universe.set_rule("life").is_err());
+    assert_eq!(universe.rule(), "B3/S23");
+
+    let highlife = Universe::from_rle("x = 3, y = 1, rule = B36/S23\n3o!\n", 5, 5).unwrap();
+    assert_eq!(highlife.rule(), "B36/S23");
+    assert!(highlife.to_rle().contains("rule = B36/S23"));
+}
+
+#[test]
+fn rule_presets() {
+    let mut universe = Universe::new_empty(6, 6).unwrap();
+    universe.set_preset(RulePreset::HighLife);
+    assert_eq!(universe.rule(), "B36/S23");
+    universe.set_preset(RulePreset::LifeWithoutDeath);
+    assert_eq!(universe.rule(), "B3/S012345678");
+    universe.set_alive(0, 0).unwrap();
+    universe.tick();
+    assert!(universe.is_alive(0, 0));
+    universe.set_preset(RulePreset::Life);
+    universe.tick();
+    assert!(!universe.is_alive(0, 0));
+}
+
+#[test]
+fn generations_rules_decay() {
+    let mut universe = Universe::new_empty(6, 6).unwrap();
+    universe.set_rule("/2/3").unwrap();
+    assert_eq!(universe.rule(), "B2/S/C3");
+    assert_eq!(universe.state_count(), 3);
+    universe.set_rule("345/2/4").unwrap();
+    assert_eq!(universe.rule(), "B2/S345/C4");
+    assert!(universe.set_rule("B2/S/C1").is_err());
+
+    // Brian's Brain: live cells always start dying, then vanish
+    universe.set_rule("B2/S/C3").unwrap();
+    universe.set_alive(2, 2).unwrap();
+    universe.set_alive(2, 3).unwrap();
+    universe.tick();
+    assert_eq!(universe.state(2, 2), 2);
+    assert_eq!(universe.state(1, 2), 1);
+    assert!(universe.is_alive(2, 2));
+    universe.tick();
+    assert_eq!(universe.state(2, 2), 0);
+    assert_eq!(universe.state(1, 2), 2);
+}
+
+#[test]
+fn larger_than_life_rules() {
+    // At radius 1 Larger than Life reproduces Conway's Life
+    let mut life = Universe::new_with_seed(16, 12, 7).unwrap();
+    let mut ltl = life.duplicate();
+    ltl.set_rule("R1,C0,M0,S2..3,B3..3,NM").unwrap();
+    assert_eq!(ltl.rule(), "R1,C0,M0,S2..3,B3..3,NM");
+    for _ in 0..8 {
+        life.tick();
+        ltl.tick();
+        assert_eq!(ltl.get_cells(), life.get_cells());
+    }
+
+    // Counting the center, a lone cell survives and its diamond fills in
+    let mut universe = Universe::new_empty(7, 7).unwrap();
+    universe.set_rule("R2,C0,M1,S1..1,B1..1,NN").unwrap();
+    universe.set_alive(3, 3).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells().len() / 2, 13);
+
+    // Past a fixed edge nothing is alive: only the corners of a full grid have 3 neighbors
+    let mut full = Universe::from_cells(4, 4, &[1; 16]).unwrap();
+    full.set_boundary(Boundary::Fixed);
+    full.set_rule("R1,C0,M0,S3..3,B9..9,NM").unwrap();
+    full.tick();
+    assert_eq!(full.live_cells(), vec![0, 0, 0, 3, 3, 0, 3, 3]);
+
+    assert!(universe.set_rule("R0,C0,M0,S2..3,B3..3,NM").is_err());
+    assert!(universe.set_rule("R2,C0,M0,B3..3,NM").is_err());
+}
+
+#[test]
+fn von_neumann_neighborhood() {
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    universe.set_rule("B1/S").unwrap();
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(universe.rule(), "B1/SV");
+    universe.set_alive(2, 2).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![1, 2, 2, 1, 2, 3, 3, 2]);
+
+    universe.set_rule("B1/S").unwrap();
+    assert_eq!(universe.neighborhood(), Neighborhood::Moore);
+    universe.set_rule("b2/s/c3v").unwrap();
+    assert_eq!(universe.neighborhood(), Neighborhood::VonNeumann);
+    assert_eq!(universe.rule(), "B2/S/C3V");
+}
+
+#[test]
+fn hexagonal_neighborhood() {
+    let mut universe = Universe::new_empty(6, 6).unwrap();
+    universe.set_rule("B1/SH").unwrap();
+    assert_eq!(universe.neighborhood(), Neighborhood::Hexagonal);
+    assert_eq!(universe.rule(), "B1/SH");
+    universe.set_alive(2, 2).unwrap();
+    universe.tick();
+    assert_eq!(
+        universe.live_cells(),
+        vec![1, 1, 1, 2, 2, 1, 2, 3, 3, 1, 3, 2]
+    );
+
+    // Odd rows lean the other way
+    universe.clear();
+    universe.set_alive(1, 2).unwrap();
+    universe.tick();
+    assert_eq!(
+        universe.live_cells(),
+        vec![0, 2, 0, 3, 1, 1, 1, 3, 2, 2, 2, 3]
+    );
+}
+
+#[test]
+fn convolution_kernels() {
+    let mut life = Universe::new_with_seed(12, 12, 3).unwrap();
+    let mut kernel = life.duplicate();
+    kernel
+        .set_kernel(3, &[1, 1, 1, 1, 0, 1, 1, 1, 1], 3, 3, 2, 3)
+        .unwrap();
+    for _ in 0..6 {
+        life.tick();
+        kernel.tick();
+        assert_eq!(kernel.get_cells(), life.get_cells());
+    }
+
+    // A negative weight two cells to the right cancels out a birth
+    let mut universe = Universe::new_empty(7, 7).unwrap();
+    #[rustfmt::skip]
+    let weights = [
+        0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0,
+        0, 1, 0, 0, -1,
+        0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0,
+    ];
+    universe.set_kernel(5, &weights, 1, 1, 1, 1).unwrap();
+    universe.set_alive(3, 2).unwrap();
+    universe.set_alive(3, 5).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![3, 6]);
+
+    assert_eq!(
+        universe.set_kernel(4, &[1; 16], 1, 1, 1, 1),
+        Err(Error::InvalidKernel {
+            size: 4,
+            weights: 16
+        })
+    );
+    assert!(universe.set_kernel(3, &[1; 8], 1, 1, 1, 1).is_err());
+}
+
+#[test]
+fn wireworld_matches_its_rule_table() {
+    let mut native = Universe::new_empty(10, 8).unwrap();
+    native.set_wireworld();
+    assert_eq!(native.state_count(), 4);
+    native.draw_wire(1, 1, 1, 8, WireState::Conductor);
+    native.draw_wire(1, 8, 6, 8, WireState::Conductor);
+    native.draw_wire(6, 8, 6, 1, WireState::Conductor);
+    native.draw_wire(6, 1, 1, 1, WireState::Conductor);
+    native.draw_wire(3, 0, 3, 9, WireState::Conductor);
+    native.set_state(1, 3, WireState::Tail as u8).unwrap();
+    native.set_state(1, 4, WireState::Head as u8).unwrap();
+
+    let mut table = native.duplicate();
+    table.set_rule_table(WIREWORLD).unwrap();
+    for _ in 0..20 {
+        native.tick();
+        table.tick();
+        let states: Vec<u8> = (0..80).map(|i| native.state(i / 10, i % 10)).collect();
+        let expected: Vec<u8> = (0..80).map(|i| table.state(i / 10, i % 10)).collect();
+        assert_eq!(states, expected);
+    }
+    assert_eq!(native.rule(), "WireWorld");
+}
+
+#[test]
+fn langtons_ant() {
+    let mut universe = Universe::new_empty(11, 11).unwrap();
+    universe.add_ant(5, 5, Direction::Up).unwrap();
+    assert_eq!(universe.rule(), "RL");
+    for _ in 0..5 {
+        universe.tick();
+    }
+    assert_eq!(universe.live_cells(), vec![5, 6, 6, 5, 6, 6]);
+    assert_eq!(universe.ants(), vec![5, 4, Direction::Left as u32]);
+
+    universe.set_ant_rule("LLRR").unwrap();
+    assert_eq!(universe.state_count(), 4);
+    assert_eq!(universe.ants().len(), 3);
+    assert!(universe.set_ant_rule("RX").is_err());
+    assert!(universe.set_ant_rule("R").is_err());
+
+    // Ants leave through a fixed edge
+    universe.clear_ants();
+    universe.set_boundary(Boundary::Fixed);
+    universe.add_ant(0, 0, Direction::Right).unwrap();
+    universe.tick();
+    assert!(universe.ants().is_empty());
+}
+
+#[test]
+fn ants_follow_transforms() {
+    let mut universe = Universe::new_empty(6, 3).unwrap();
+    universe.add_ant(1, 4, Direction::Up).unwrap();
+    universe.add_ant(2, 0, Direction::Left).unwrap();
+    universe.rotate_cw();
+    assert_eq!(
+        universe.ants(),
+        vec![4, 1, Direction::Right as u32, 0, 0, Direction::Up as u32]
+    );
+    universe.tick();
+
+    // Ants whose cell is cut off are dropped
+    universe.resize(3, 3, Anchor::BottomLeft).unwrap();
+    assert_eq!(universe.ants(), vec![2, 1, Direction::Down as u32]);
+    universe.tick();
+}
+
+#[test]
+fn elementary_automata_scroll_down() {
+    let mut universe = Universe::new_empty(9, 4).unwrap();
+    universe.set_alive(0, 4).unwrap();
+    universe.set_elementary_rule(90);
+    assert_eq!(universe.rule(), "W90");
+    universe.tick();
+    universe.tick();
+    universe.tick();
+    assert_eq!(
+        universe.to_string(),
+        "◻◻◻◻◼◻◻◻◻\n◻◻◻◼◻◼◻◻◻\n◻◻◼◻◻◻◼◻◻\n◻◼◻◼◻◼◻◼◻\n"
+    );
+
+    // Past the bottom the picture scrolls up a row per generation
+    universe.tick();
+    assert_eq!(
+        universe.to_string(),
+        "◻◻◻◼◻◼◻◻◻\n◻◻◼◻◻◻◼◻◻\n◻◼◻◼◻◼◻◼◻\n◼◻◻◻◻◻◻◻◼\n"
+    );
+    assert_eq!(
+        universe.changed_cells(),
+        vec![
+            0, 3, 0, 4, 0, 5, 1, 2, 1, 3, 1, 5, 1, 6, 2, 1, 2, 2, 2, 3, 2, 5, 2, 6, 2, 7, 3, 0, 3,
+            1, 3, 3, 3, 5, 3, 7, 3, 8
+        ]
+    );
+
+    universe.set_rule("w30").unwrap();
+    assert_eq!(universe.rule(), "W30");
+
+    // Cutting off the newest generation restarts from the lowest row left
+    let mut universe = Universe::new_empty(8, 8).unwrap();
+    universe.set_alive(7, 3).unwrap();
+    universe.set_alive(2, 3).unwrap();
+    universe.set_elementary_rule(90);
+    universe.resize(8, 4, Anchor::TopLeft).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![2, 3, 3, 2, 3, 4]);
+}
+
+#[test]
+fn colored_life_variants() {
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    universe.set_rule("QuadLife").unwrap();
+    assert_eq!(universe.state_count(), 5);
+    for (col, color) in [(1, 1), (2, 2), (3, 3)] {
+        universe.set_state(2, col, color).unwrap();
+    }
+    universe.tick();
+    assert_eq!(universe.state(1, 2), 4);
+    assert_eq!(universe.state(2, 2), 2);
+    assert_eq!(universe.state(3, 2), 4);
+
+    universe.set_colors(2).unwrap();
+    assert_eq!(universe.rule(), "Immigration");
+    universe.clear();
+    for (col, color) in [(1, 2), (2, 1), (3, 2)] {
+        universe.set_state(2, col, color).unwrap();
+    }
+    universe.tick();
+    assert_eq!(universe.state(1, 2), 2);
+    assert_eq!(universe.state(2, 2), 1);
+
+    universe.set_rule("B36/S23").unwrap();
+    universe.set_colors(3).unwrap();
+    assert_eq!(universe.rule(), "B36/S23 in 3 colors");
+    assert!(universe.set_colors(1).is_err());
+}
+
+#[test]
+fn stochastic_rules() {
+    let mut universe = Universe::new_with_seed(8, 8, 5).unwrap();
+    universe.clear();
+    universe.insert_pattern("block", 2, 2).unwrap();
+    universe.set_rule_probabilities(1.0, 0.0);
+    universe.tick();
+    assert!(universe.live_cells().is_empty());
+
+    // Same seed, same run
+    let mut a = Universe::new_with_seed(16, 16, 11).unwrap();
+    a.set_rule_probabilities(0.9, 0.95);
+    let mut b = a.duplicate();
+    for _ in 0..10 {
+        a.tick();
+        b.tick();
+    }
+    assert_eq!(a.get_cells(), b.get_cells());
+    assert_eq!(a.survival_probability(), 0.95);
+
+    // Certain rules draw nothing from the generator
+    let mut plain = Universe::new_with_seed(8, 8, 2).unwrap();
+    let mut copy = plain.duplicate();
+    copy.set_rule_probabilities(2.0, 1.0);
+    assert_eq!(copy.birth_probability(), 1.0);
+    plain.tick();
+    copy.tick();
+    plain.randomize(0.5);
+    copy.randomize(0.5);
+    assert_eq!(plain.get_cells(), copy.get_cells());
+}
+
+#[test]
+fn noise_flips_cells_after_each_tick() {
+    let mut universe = Universe::new_with_seed(4, 4, 1).unwrap();
+    universe.clear();
+    universe.set_noise(1.0);
+    universe.tick();
+    assert_eq!(universe.live_cells().len(), 32);
+    assert_eq!(universe.changed_cells().len(), 32);
+
+    let mut universe = Universe::new_with_seed(100, 100, 8).unwrap();
+    universe.clear();
+    universe.set_noise(0.01);
+    universe.tick();
+    let flipped = universe.changed_cells().len() / 2;
+    assert!((40..200).contains(&flipped), "{} cells flipped", flipped);
+    assert_eq!(universe.live_cells().len() / 2, flipped);
+
+    universe.set_noise(0.0);
+    assert_eq!(universe.noise(), 0.0);
+
+    // Tiny probabilities must not round to flipping everything
+    let mut universe = Universe::new_empty(20, 20).unwrap();
+    universe.set_noise(1e-17);
+    universe.tick();
+    assert!(universe.live_cells().is_empty());
+    universe.set_noise(f64::NAN);
+    assert_eq!(universe.noise(), 0.0);
+
+    // Walls and anchors keep their state
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.set_wall(0, 0, true).unwrap();
+    universe.set_anchor(3, 3, true).unwrap();
+    universe.set_noise(1.0);
+    universe.tick();
+    assert!(!universe.is_alive(0, 0) && universe.is_alive(3, 3));
+    assert_eq!(universe.live_cells().len(), 30);
+}
+
+#[test]
+fn rule_schedule_loops_through_its_turns() {
+    let mut universe = Universe::new_with_seed(8, 8, 1).unwrap();
+    universe.add_scheduled_rule("B3/S23", 2).unwrap();
+    universe.add_scheduled_rule("B2/S", 1).unwrap();
+    assert_eq!(
+        universe.add_scheduled_rule("B9", 1),
+        Err(Error::InvalidRule("B9".to_string()))
+    );
+    assert_eq!(universe.schedule_len(), 2);
+
+    let mut rules = Vec::new();
+    for _ in 0..6 {
+        universe.tick();
+        rules.push(universe.rule());
+    }
+    assert_eq!(
+        rules,
+        ["B3/S23", "B3/S23", "B2/S", "B3/S23", "B3/S23", "B2/S"]
+    );
+
+    universe.clear_schedule();
+    universe.tick();
+    assert_eq!(universe.rule(), "B2/S");
+    assert_eq!(universe.schedule_len(), 0);
+}
+
+#[test]
+fn rule_zones_run_their_own_rules() {
+    let mut universe = Universe::new_with_seed(12, 12, 1).unwrap();
+    universe.clear();
+    universe.add_rule_zone(0, 6, 12, 6, "B2/S").unwrap();
+    assert!(universe.add_rule_zone(0, 0, 1, 1, "B9/S").is_err());
+    assert_eq!(universe.rule_zone_count(), 1);
+    assert_eq!(universe.rule_at(3, 3), "B3/S23");
+    assert_eq!(universe.rule_at(3, 8), "B2/S");
+
+    // A blinker in the Life half keeps oscillating, one in the Seeds half dies
+    universe.set_cells(&[(5, 2), (5, 3), (5, 4), (2, 8), (3, 8), (4, 8)]);
+    universe.tick();
+    assert!(universe.is_alive(4, 3) && universe.is_alive(6, 3));
+    for row in 2..5 {
+        assert!(!universe.is_alive(row, 8));
+    }
+
+    universe.clear_rule_zones();
+    assert_eq!(universe.rule_at(3, 8), "B3/S23");
+}
+
+#[test]
+fn two_teams_compete() {
+    let mut universe = Universe::new_with_seed(24, 24, 5).unwrap();
+    universe.clear();
+    universe.seed_team(Team::A, 0, 0, 24, 12, 0.4);
+    universe.seed_team(Team::B, 0, 12, 24, 12, 0.4);
+    assert_eq!(universe.rule(), "Immigration");
+    for _ in 0..10 {
+        let (a, b) = (
+            universe.team_population(Team::A),
+            universe.team_population(Team::B),
+        );
+        assert_eq!((a + b) as usize, universe.live_cells().len() / 2);
+        universe.tick();
+    }
+
+    // A newborn joins the team of two of its three parents
+    universe.clear();
+    universe.set_state(0, 0, 2).unwrap();
+    universe.set_state(0, 2, 2).unwrap();
+    universe.set_state(2, 1, 1).unwrap();
+    universe.tick();
+    assert_eq!(universe.state(1, 1), 2);
+    assert_eq!(universe.team_population(Team::B), 1);
+}
+
+#[test]
+fn custom_rules_plug_in_through_the_trait() {
+    let mut life = Universe::new_with_seed(20, 20, 9).unwrap();
+    let mut conway = life.duplicate();
+    conway.set_custom_rule(Conway);
+    assert_eq!(conway.rule(), "custom");
+    for _ in 0..10 {
+        life.tick();
+        conway.tick();
+    }
+    assert_eq!(life.get_cells(), conway.get_cells());
+
+    // Cells remember where they are: only the left column may come alive
+    struct LeftColumn;
+    impl Rule for LeftColumn {
+        fn next_state(&self, _cell: Cell, neighbors: NeighborInfo) -> Cell {
+            if neighbors.col == 0 {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            }
+        }
+    }
+    conway.set_custom_rule(LeftColumn);
+    conway.tick();
+    assert_eq!(conway.live_cells().len() / 2, 20);
+    assert!(conway.is_alive(7, 0));
+}
+
+#[test]
+fn cyclic_cells_advance_when_neighbors_lead() {
+    let mut universe = Universe::new_with_seed(5, 5, 3).unwrap();
+    universe.clear();
+    assert!(universe.set_cyclic_rule(1, 1).is_err());
+    assert!(universe.set_cyclic_rule(3, 0).is_err());
+    universe.set_cyclic_rule(3, 1).unwrap();
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(universe.rule(), "R1/T1/C3/NN");
+    assert_eq!(universe.state_count(), 3);
+
+    universe.set_state(2, 2, 1).unwrap();
+    universe.tick();
+    assert_eq!(universe.live_cells().len() / 2, 5);
+    assert_eq!(universe.state(1, 2), 1);
+    assert_eq!(universe.state(1, 1), 0);
+
+    // State 2 wraps around to 0 once a neighbor has
+    universe.set_state(0, 0, 2).unwrap();
+    universe.tick();
+    assert_eq!(universe.state(0, 0), 0);
+
+    universe.set_cyclic_rule(16, 1).unwrap();
+    universe.randomize_states();
+    let states: Vec<u8> = (0..25)
+        .map(|idx| universe.state(idx / 5, idx % 5))
+        .collect();
+    assert!(states.iter().all(|&state| state < 16));
+    assert!(states.iter().filter(|&&state| state > 2).count() > 5);
+}
+
+#[test]
+fn forest_fires_spread_and_regrow() {
+    let mut universe = Universe::new_with_seed(6, 6, 2).unwrap();
+    universe.clear();
+    universe.set_forest_fire(0.0, 0.0);
+    assert_eq!(universe.state_count(), 3);
+    for col in 0..6 {
+        universe.set_state(3, col, 1).unwrap();
+    }
+    universe.set_state(3, 0, 2).unwrap();
+    universe.tick();
+    assert_eq!(universe.state(3, 0), 0);
+    assert_eq!(universe.state(3, 1), 2);
+    assert_eq!(universe.state(3, 2), 1);
+    universe.tick();
+    assert_eq!(universe.state(3, 1), 0);
+    assert_eq!(universe.state(3, 2), 2);
+
+    universe.set_forest_fire(1.0, 0.0);
+    universe.tick();
+    assert_eq!(universe.state(0, 0), 1);
+
+    let mut forest = Universe::new_with_seed(32, 32, 4).unwrap();
+    forest.set_forest_fire(0.05, 0.001);
+    let mut copy = forest.duplicate();
+    for _ in 0..20 {
+        forest.tick();
+        copy.tick();
+    }
+    assert_eq!(forest.get_cells(), copy.get_cells());
+}
+
+#[test]
+fn self_inclusive_counting() {
+    let mut vote = Universe::new_with_seed(24, 24, 6).unwrap();
+    vote.set_preset(RulePreset::Vote);
+    let mut inclusive = vote.duplicate();
+    inclusive.set_rule("B5678/S56789").unwrap();
+    inclusive.set_count_self(true);
+    assert!(inclusive.counts_self());
+    assert_eq!(inclusive.rule(), "B5678/S56789");
+    for _ in 0..5 {
+        vote.tick();
+        inclusive.tick();
+    }
+    assert_eq!(vote.get_cells(), inclusive.get_cells());
+
+    // Only a live cell can see nine, so births on 9 are rejected
+    assert!(inclusive.set_rule("B59/S5").is_err());
+    inclusive.set_preset(RulePreset::Anneal);
+    assert!(!inclusive.counts_self());
+}
+
+#[test]
+fn isotropic_rules_in_hensel_notation() {
+    // Without letters Hensel notation is the ordinary rule
+    let mut life = Universe::new_with_seed(24, 24, 10).unwrap();
+    let mut totalistic = life.duplicate();
+    totalistic.set_rule("B3/S23-").unwrap();
+    for _ in 0..10 {
+        life.tick();
+        totalistic.tick();
+    }
+    assert_eq!(life.get_cells(), totalistic.get_cells());
+
+    // Just Friends: two neighbors give birth unless they touch
+    let mut universe = Universe::new_with_seed(8, 8, 1).unwrap();
+    universe.set_rule("B2-a/S12").unwrap();
+    assert_eq!(universe.rule(), "B2-a/S12");
+    universe.clear();
+    universe.set_cells(&[(2, 2), (2, 3)]);
+    universe.tick();
+    assert_eq!(universe.live_cells(), [2, 2, 2, 3]);
+
+    universe.clear();
+    universe.set_cells(&[(2, 2), (2, 4)]);
+    universe.tick();
+    assert!(universe.is_alive(1, 3) && universe.is_alive(3, 3));
+
+    assert!(universe.set_rule("B2x/S").is_err());
+    assert!(universe.set_rule("B1k/S").is_err());
+}
+
+#[test]
+fn active_cell_ticks_match_full_ticks() {
+    // Edits, rule changes and walls between ticks must all be picked up
+    let mut universe = Universe::new_with_seed(40, 30, 11).unwrap();
+    universe.clear();
+    universe.insert_pattern("glider", 5, 5).unwrap();
+    universe.insert_pattern("r_pentomino", 20, 20).unwrap();
+    let mut reference = universe.duplicate();
+    let check = |universe: &mut Universe, reference: &mut Universe| {
+        universe.tick();
+        // Dropping the spare buffer makes the reference recompute every cell
+        reference.shrink_to_fit();
+        reference.tick();
+        assert_eq!(universe.get_cells(), reference.get_cells());
+        assert_eq!(universe.changed_cells(), reference.changed_cells());
+    };
+
+    for generation in 0..60 {
+        match generation {
+            10 => {
+                universe.toggle_cell(0, 0).unwrap();
+                reference.toggle_cell(0, 0).unwrap();
+            }
+            20 => {
+                universe.set_rule("B36/S23").unwrap();
+                reference.set_rule("B36/S23").unwrap();
+            }
+            30 => {
+                universe.set_wall(21, 21, true).unwrap();
+                reference.set_wall(21, 21, true).unwrap();
+            }
+            40 => {
+                universe.set_boundary(Boundary::Fixed);
+                reference.set_boundary(Boundary::Fixed);
+            }
+            _ => {}
+        }
+        check(&mut universe, &mut reference);
+    }
+}
+
+#[test]
+fn active_cell_ticks_follow_transforms() {
+    // An empty grid looks the same after turning, but its size does not
+    let mut universe = Universe::new_empty(2, 4).unwrap();
+    universe.set_alive(3, 1).unwrap();
+    universe.tick();
+    universe.rotate_cw();
+    universe.tick();
+    assert_eq!(universe.live_cells(), [] as [u32; 0]);
+
+    // Cells written through indexing are picked up too
+    let mut universe = Universe::new_empty(8, 8).unwrap();
+    universe.insert_pattern("blinker", 2, 2).unwrap();
+    universe.tick();
+    universe[(6, 5)] = Cell::Alive;
+    universe[(6, 6)] = Cell::Alive;
+    universe[(6, 4)] = Cell::Alive;
+    universe.tick();
+    assert_eq!(universe.live_cells(), [2, 2, 2, 3, 2, 4, 5, 5, 6, 5, 7, 5]);
+}
+
+#[test]
+fn tiled_ticks_match_full_ticks() {
+    // Soups change too many cells to track one by one, so the tiles around them
+    // are redone, including across the wrapping corner
+    let mut universe = Universe::new_with_seed(320, 256, 5).unwrap();
+    universe.clear();
+    universe.randomize_region(60, 60, 40, 40, 0.5);
+    universe.randomize_region(0, 0, 24, 24, 0.5);
+    let mut reference = universe.duplicate();
+    for _ in 0..60 {
+        universe.tick();
+        reference.shrink_to_fit();
+        reference.tick();
+        assert_eq!(universe.get_cells(), reference.get_cells());
+        assert_eq!(universe.changed_cells(), reference.changed_cells());
+    }
+}
+
+#[test]
+fn dirty_tiles_cover_changed_cells() {
+    let mut universe = Universe::new_with_seed(70, 40, 1).unwrap();
+    universe.clear();
+    // A blinker straddling the first two tiles
+    universe.insert_pattern("blinker", 10, 31).unwrap();
+    universe.tick();
+    let size = universe.tile_size();
+    assert_eq!(
+        universe.dirty_tiles(),
+        [0, 0, size, size, 0, size, size, size]
+    );
+
+    // Tiles at the edges are clipped to the grid
+    universe.clear();
+    universe.tick();
+    universe.insert_pattern("blinker", 38, 66).unwrap();
+    universe.tick();
+    assert_eq!(
+        universe.dirty_tiles(),
+        [size, 2 * size, 40 - size, 70 - 2 * size]
+    );
+}
@@ -0,0 +1,44 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Cell, Error, Universe};
+
+fn grid(text: &str) -> Vec<Cell> {
+    Universe::from_text(text).unwrap().get_cells().to_vec()
+}
+
+#[test]
+fn operations_need_a_selection() {
+    let mut universe = Universe::from_text("OO..\nO...\n....").unwrap();
+    assert!(!universe.has_selection());
+    assert_eq!(universe.clear_selection(), Err(Error::NoSelection));
+    assert_eq!(universe.selection_to_rle(), Err(Error::NoSelection));
+
+    universe.select(2, 2, 5, 5);
+    assert_eq!(universe.selection(), vec![2, 2, 1, 2]);
+    universe.select(3, 0, 1, 1);
+    assert!(!universe.has_selection());
+}
+
+#[test]
+fn edits_act_on_the_selection() {
+    let mut universe = Universe::from_text("OO..\nO...\n....\n....").unwrap();
+    universe.select(0, 0, 2, 3);
+    assert_eq!(universe.selection_cells().unwrap(), vec![0, 0, 0, 1, 1, 0]);
+    assert_eq!(universe.copy_selection().unwrap().width(), 3);
+    assert_eq!(
+        universe.selection_to_rle().unwrap(),
+        "x = 3, y = 2, rule = B3/S23\n2o$o!\n"
+    );
+
+    universe.flip_selection_horizontal().unwrap();
+    assert_eq!(universe.get_cells(), &grid(".OO.\n..O.\n....\n....")[..]);
+    universe.undo();
+
+    universe.rotate_selection_cw().unwrap();
+    assert_eq!(universe.get_cells(), &grid("OO..\n.O..\n....\n....")[..]);
+    assert_eq!(universe.selection(), vec![0, 0, 3, 2]);
+
+    universe.invert_selection().unwrap();
+    assert_eq!(universe.get_cells(), &grid("....\nO...\nOO..\n....")[..]);
+    universe.clear_selection().unwrap();
+    assert!(universe.live_cells().is_empty());
+}
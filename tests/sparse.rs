@@ -0,0 +1,24 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::SparseUniverse;
+
+#[test]
+fn glider_travels_without_wrapping() {
+    let mut universe = SparseUniverse::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    assert_eq!(universe.bounds(), vec![0, 0, 2, 2]);
+
+    // A glider moves one cell diagonally every 4 generations
+    for _ in 0..400 {
+        universe.tick();
+    }
+    assert_eq!(universe.population(), 5);
+    assert_eq!(universe.generation(), 400);
+    assert_eq!(universe.bounds(), vec![100, 100, 102, 102]);
+
+    let shifted = SparseUniverse::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    let expected: Vec<i32> = shifted
+        .live_cells()
+        .iter()
+        .map(|&coord| coord + 100)
+        .collect();
+    assert_eq!(universe.live_cells(), expected);
+}
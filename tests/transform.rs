@@ -0,0 +1,80 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{Anchor, Cell, Error, Universe};
+
+fn grid(text: &str) -> Vec<Cell> {
+    Universe::from_text(text).unwrap().get_cells().to_vec()
+}
+
+#[test]
+fn shift_wraps_around_edges() {
+    let mut universe = Universe::from_text("O...\n.O..\n...O").unwrap();
+    universe.shift(1, -1);
+    assert_eq!(universe.get_cells(), &grid("..O.\n...O\nO...")[..]);
+    universe.shift(-4, 5);
+    assert_eq!(universe.get_cells(), &grid("O...\n.O..\n...O")[..]);
+}
+
+#[test]
+fn rotations_swap_dimensions() {
+    let mut universe = Universe::from_text("OO.\n..O").unwrap();
+    universe.rotate_cw();
+    assert_eq!((universe.width(), universe.height()), (2, 3));
+    assert_eq!(universe.get_cells(), &grid(".O\n.O\nO.")[..]);
+
+    universe.rotate_ccw();
+    assert_eq!(universe.get_cells(), &grid("OO.\n..O")[..]);
+    universe.rotate_ccw();
+    assert_eq!(universe.get_cells(), &grid(".O\nO.\nO.")[..]);
+}
+
+#[test]
+fn flips_mirror_the_grid() {
+    let mut universe = Universe::from_text("OO.\n..O").unwrap();
+    universe.flip_horizontal();
+    assert_eq!(universe.get_cells(), &grid(".OO\nO..")[..]);
+    universe.flip_vertical();
+    assert_eq!(universe.get_cells(), &grid("O..\n.OO")[..]);
+}
+
+#[test]
+fn crop_to_content_fits_live_cells() {
+    let mut universe = Universe::from_text("......\n..O...\n...OO.\n......").unwrap();
+    universe.crop_to_content(0).unwrap();
+    assert_eq!((universe.width(), universe.height()), (3, 2));
+    assert_eq!(universe.get_cells(), &grid("O..\n.OO")[..]);
+
+    universe.crop_to_content(1).unwrap();
+    assert_eq!(
+        universe.get_cells(),
+        &grid(".....\n.O...\n..OO.\n.....")[..]
+    );
+    assert_eq!(
+        universe.crop_to_content(u32::MAX / 2),
+        Err(Error::InvalidSize {
+            width: u32::MAX,
+            height: u32::MAX
+        })
+    );
+    assert_eq!((universe.width(), universe.height()), (5, 4));
+
+    universe.clear();
+    assert_eq!(universe.crop_to_content(0), Err(Error::NoLiveCells));
+}
+
+#[test]
+fn resize_preserves_content_around_anchor() {
+    let source = || Universe::from_text("O.\n.O").unwrap();
+
+    let mut padded = source();
+    padded.resize(4, 3, Anchor::BottomRight).unwrap();
+    assert_eq!(padded.get_cells(), &grid("....\n..O.\n...O")[..]);
+
+    let mut centered = source();
+    centered.resize(4, 4, Anchor::Center).unwrap();
+    assert_eq!(centered.get_cells(), &grid("....\n.O..\n..O.\n....")[..]);
+
+    let mut cropped = source();
+    cropped.resize(1, 1, Anchor::TopRight).unwrap();
+    assert_eq!(cropped.get_cells(), &[Cell::Dead]);
+    assert!(cropped.resize(0, 1, Anchor::TopLeft).is_err());
+}
@@ -0,0 +1,571 @@
+extern crate wasm_game_of_life;
+use wasm_game_of_life::{
+    BlendMode, Boundary, Cell, Error, Gradient, Pcg32, Rng, RngKind, Symmetry, Universe,
+    UniverseBuilder, XorShift64,
+};
+
+#[test]
+fn new_with_size_rejects_empty_dimensions() {
+    assert_eq!(
+        Universe::new_with_size(0, 10).err(),
+        Some(Error::InvalidSize {
+            width: 0,
+            height: 10
+        })
+    );
+    assert!(Universe::new_with_size(10, 0).is_err());
+}
+
+#[test]
+fn new_empty_has_no_live_cells() {
+    let universe = Universe::new_empty(7, 3).unwrap();
+    assert_eq!(universe.width(), 7);
+    assert_eq!(universe.height(), 3);
+    assert!(universe.get_cells().iter().all(|&c| c == Cell::Dead));
+}
+
+#[test]
+fn seeded_universes_are_reproducible() {
+    let a = Universe::new_with_seed(32, 16, 42).unwrap();
+    let b = Universe::new_with_seed(32, 16, 42).unwrap();
+    let c = Universe::new_with_seed(32, 16, 43).unwrap();
+    assert_eq!(a.get_cells(), b.get_cells());
+    assert_ne!(a.get_cells(), c.get_cells());
+    assert!(a.get_cells().contains(&Cell::Alive));
+}
+
+#[test]
+fn randomize_honors_density() {
+    let mut universe = Universe::new_with_seed(100, 100, 7).unwrap();
+    let live = |u: &Universe| u.get_cells().iter().filter(|&&c| c == Cell::Alive).count();
+
+    universe.randomize(0.1);
+    assert!((700..1300).contains(&live(&universe)));
+    universe.randomize(0.8);
+    assert!((7500..8500).contains(&live(&universe)));
+    universe.randomize(0.0);
+    assert_eq!(live(&universe), 0);
+    universe.randomize(1.0);
+    assert_eq!(live(&universe), 10_000);
+}
+
+#[test]
+fn builder_applies_options() {
+    let universe = UniverseBuilder::new()
+        .size(12, 8)
+        .seed(1)
+        .density(0.0)
+        .boundary(Boundary::Fixed)
+        .pattern("glider", 0, 0)
+        .build()
+        .unwrap();
+
+    assert_eq!((universe.width(), universe.height()), (12, 8));
+    assert_eq!(universe.boundary(), Boundary::Fixed);
+    let live = universe
+        .get_cells()
+        .iter()
+        .filter(|&&c| c == Cell::Alive)
+        .count();
+    assert_eq!(live, 5);
+
+    let unknown = UniverseBuilder::new().pattern("nope", 0, 0).build();
+    assert_eq!(
+        unknown.err(),
+        Some(Error::UnknownPattern("nope".to_string()))
+    );
+}
+
+#[test]
+fn fixed_boundary_does_not_wrap() {
+    // A blinker on the left edge oscillates across the seam when wrapping
+    let mut wrapped = Universe::new_empty(5, 5).unwrap();
+    wrapped.set_cells(&[(1, 0), (2, 0), (3, 0)]);
+    let mut fixed = Universe::new_empty(5, 5).unwrap();
+    fixed.set_boundary(Boundary::Fixed);
+    fixed.set_cells(&[(0, 0), (0, 1), (1, 0)]);
+    wrapped.tick();
+    fixed.tick();
+
+    let mut expected = Universe::new_empty(5, 5).unwrap();
+    expected.set_cells(&[(2, 4), (2, 0), (2, 1)]);
+    assert_eq!(wrapped.get_cells(), expected.get_cells());
+
+    // An L-tromino in the corner becomes a block only when the far edges are dead
+    let mut block = Universe::new_empty(5, 5).unwrap();
+    block.set_cells(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(fixed.get_cells(), block.get_cells());
+}
+
+#[test]
+fn from_cells_round_trips_buffer() {
+    let buffer = [0, 1, 0, 0, 0, 1, 1, 1, 1];
+    let universe = Universe::from_cells(3, 3, &buffer).unwrap();
+    let restored: Vec<u8> = universe.get_cells().iter().map(|&c| c as u8).collect();
+    assert_eq!(restored, buffer);
+
+    assert_eq!(
+        Universe::from_cells(3, 3, &buffer[..8]).err(),
+        Some(Error::CellCount {
+            expected: 9,
+            actual: 8
+        })
+    );
+}
+
+#[test]
+fn from_text_parses_render_output() {
+    let mut universe = Universe::new_empty(6, 4).unwrap();
+    universe.set_cells(&[(0, 1), (2, 5), (3, 0)]);
+    let parsed = Universe::from_text(&universe.render()).unwrap();
+    assert_eq!((parsed.width(), parsed.height()), (6, 4));
+    assert_eq!(parsed.get_cells(), universe.get_cells());
+
+    let plain = Universe::from_text(".O.\n..O\nOOO\n").unwrap();
+    let mut glider = Universe::new_empty(3, 3).unwrap();
+    glider.set_cells(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    assert_eq!(plain.get_cells(), glider.get_cells());
+
+    assert!(matches!(
+        Universe::from_text("..\n.x"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn noise_initialization_is_clumpy() {
+    let mut universe = Universe::new_with_seed(64, 64, 3).unwrap();
+    universe.randomize_noise(8.0, 0.5);
+
+    let cells = universe.get_cells();
+    let live = cells.iter().filter(|&&c| c == Cell::Alive).count();
+    assert!(live > 0 && live < cells.len());
+
+    // Neighbors agree far more often than in uniform soup (~50%)
+    let same = cells
+        .chunks(64)
+        .flat_map(|row| row.windows(2))
+        .filter(|pair| pair[0] == pair[1])
+        .count();
+    assert!(same as f64 / (64.0 * 63.0) > 0.8);
+}
+
+#[test]
+fn symmetric_soups_are_symmetric() {
+    let mut universe = Universe::new_with_seed(9, 9, 11).unwrap();
+    universe.randomize_symmetric(0.5, Symmetry::D8).unwrap();
+    let cells = universe.get_cells();
+    let at = |r: usize, c: usize| cells[r * 9 + c];
+    for r in 0..9 {
+        for c in 0..9 {
+            assert_eq!(at(r, c), at(c, r));
+            assert_eq!(at(r, c), at(r, 8 - c));
+            assert_eq!(at(r, c), at(8 - r, 8 - c));
+        }
+    }
+
+    let mut wide = Universe::new_with_seed(10, 6, 11).unwrap();
+    wide.randomize_symmetric(0.5, Symmetry::C2).unwrap();
+    let cells = wide.get_cells();
+    for i in 0..cells.len() {
+        assert_eq!(cells[i], cells[cells.len() - 1 - i]);
+    }
+    assert_eq!(
+        wide.randomize_symmetric(0.5, Symmetry::C4).err(),
+        Some(Error::NotSquare {
+            width: 10,
+            height: 6
+        })
+    );
+}
+
+#[test]
+fn gradient_soup_varies_density() {
+    let mut universe = Universe::new_with_seed(100, 50, 5).unwrap();
+    universe.randomize_gradient(0.0, 1.0, Gradient::Horizontal);
+    let cells = universe.get_cells();
+    let live_in_cols = |cols: std::ops::Range<usize>| {
+        cells
+            .chunks(100)
+            .flat_map(|row| row[cols.clone()].iter())
+            .filter(|&&c| c == Cell::Alive)
+            .count()
+    };
+
+    assert!(live_in_cols(0..20) * 4 < live_in_cols(80..100));
+    assert!(cells
+        .chunks(100)
+        .all(|row| row[99] == Cell::Alive && row[0] == Cell::Dead));
+}
+
+#[test]
+fn from_image_thresholds_luminance() {
+    #[rustfmt::skip]
+    let rgba = [
+        0, 0, 0, 255,       255, 255, 255, 255,
+        200, 30, 30, 255,   0, 0, 0, 0,
+    ];
+    let universe = Universe::from_image(2, 2, &rgba, 128).unwrap();
+    assert_eq!(
+        universe.get_cells(),
+        &[Cell::Alive, Cell::Dead, Cell::Alive, Cell::Dead]
+    );
+    assert!(matches!(
+        Universe::from_image(2, 2, &rgba[..12], 128),
+        Err(Error::ImageSize {
+            expected: 16,
+            actual: 12
+        })
+    ));
+}
+
+#[test]
+fn stamp_text_draws_glyphs() {
+    let mut universe = Universe::new_empty(20, 10).unwrap();
+    universe.stamp_text(1, 2, "Hi");
+    let expected = Universe::from_text(
+        "\
+....................
+..O...O..OOO........
+..O...O...O.........
+..O...O...O.........
+..OOOOO...O.........
+..O...O...O.........
+..O...O...O.........
+..O...O..OOO........
+....................
+....................",
+    )
+    .unwrap();
+    assert_eq!(universe.get_cells(), expected.get_cells());
+}
+
+#[test]
+fn randomize_region_leaves_outside_untouched() {
+    let mut universe = Universe::new_empty(10, 10).unwrap();
+    universe.randomize_region(6, 7, 10, 10, 1.0);
+    let cells = universe.get_cells();
+    for r in 0..10 {
+        for c in 0..10 {
+            let inside = r >= 6 && c >= 7;
+            assert_eq!(cells[r * 10 + c] == Cell::Alive, inside);
+        }
+    }
+}
+
+#[test]
+fn clear_keeps_buffer_and_settings() {
+    let mut universe = Universe::new_with_seed(8, 8, 1).unwrap();
+    universe.set_boundary(Boundary::Fixed);
+    let ptr = universe.cells();
+    universe.clear();
+    assert_eq!(universe.cells(), ptr);
+    assert_eq!(universe.boundary(), Boundary::Fixed);
+    assert!(universe.get_cells().iter().all(|&c| c == Cell::Dead));
+}
+
+#[test]
+fn reset_restores_initial_state() {
+    let mut universe = Universe::new_with_seed(16, 16, 9).unwrap();
+    let initial = universe.get_cells().to_vec();
+    for _ in 0..5 {
+        universe.tick();
+    }
+    universe.randomize(0.3);
+    let randomized = universe.get_cells().to_vec();
+
+    let ptr = universe.cells();
+    universe.reset();
+    assert_eq!(universe.get_cells(), &initial[..]);
+    assert_eq!(universe.cells(), ptr);
+
+    // The generator is rewound too, so the same calls replay the same soup
+    for _ in 0..5 {
+        universe.tick();
+    }
+    universe.randomize(0.3);
+    assert_eq!(universe.get_cells(), &randomized[..]);
+}
+
+#[test]
+fn rng_backends_are_reproducible_and_distinct() {
+    let xorshift = Universe::new_with_rng(16, 16, RngKind::XorShift, 5).unwrap();
+    let pcg = Universe::new_with_rng(16, 16, RngKind::Pcg, 5).unwrap();
+    let pcg_again = Universe::new_with_rng(16, 16, RngKind::Pcg, 5).unwrap();
+    assert_eq!(pcg.rng_kind(), RngKind::Pcg);
+    assert_eq!(pcg.get_cells(), pcg_again.get_cells());
+    assert_ne!(pcg.get_cells(), xorshift.get_cells());
+
+    // The trait is usable directly from Rust
+    let mut rng = Pcg32::new(1);
+    let x = rng.next_f64();
+    assert!((0.0..1.0).contains(&x));
+    assert_ne!(XorShift64::new(1).next_u64(), XorShift64::new(2).next_u64());
+}
+
+#[test]
+fn duplicate_branches_independently() {
+    let mut original = Universe::new_with_seed(16, 16, 2).unwrap();
+    original.set_boundary(Boundary::Fixed);
+    let mut branch = original.duplicate();
+    assert_eq!(branch.get_cells(), original.get_cells());
+    assert_eq!(branch.boundary(), Boundary::Fixed);
+
+    branch.clear();
+    assert!(original.get_cells().contains(&Cell::Alive));
+    // Both copies continue the same random sequence
+    original.randomize(0.5);
+    let mut other = original.duplicate();
+    original.randomize(0.5);
+    other.randomize(0.5);
+    assert_eq!(original.get_cells(), other.get_cells());
+}
+
+#[test]
+fn reserved_capacity_survives_resizes() {
+    let mut universe = Universe::new_empty(16, 16).unwrap();
+    universe.reserve(128, 128).unwrap();
+    assert!(universe.capacity() >= 128 * 128);
+
+    let ptr = universe.cells();
+    universe.set_width(100);
+    universe.set_height(120);
+    assert_eq!(universe.cells(), ptr);
+    universe.tick();
+    assert!(universe.capacity() >= 128 * 128);
+
+    universe.shrink_to_fit();
+    assert!(universe.capacity() < 128 * 128);
+    assert!(universe.reserve(0, 10).is_err());
+}
+
+#[test]
+fn get_cell_is_bounds_checked() {
+    let mut universe = Universe::new_empty(4, 3).unwrap();
+    universe.set_cells(&[(2, 3)]);
+    assert_eq!(universe.get_cell(2, 3), Ok(Cell::Alive));
+    assert_eq!(universe.get_cell(0, 0), Ok(Cell::Dead));
+    assert_eq!(
+        universe.get_cell(3, 0),
+        Err(Error::OutOfBounds { row: 3, col: 0 })
+    );
+    assert!(universe.is_alive(2, 3));
+    assert!(!universe.is_alive(2, 4));
+}
+
+#[test]
+fn toggle_cell_flips_state() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.toggle_cell(1, 2).unwrap();
+    assert!(universe.is_alive(1, 2));
+    universe.toggle_cell(1, 2).unwrap();
+    assert!(!universe.is_alive(1, 2));
+    assert!(universe.toggle_cell(4, 0).is_err());
+}
+
+#[test]
+fn set_cell_variants() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.set_alive(0, 0).unwrap();
+    universe.set_cell(3, 3, Cell::Alive).unwrap();
+    assert!(universe.is_alive(0, 0) && universe.is_alive(3, 3));
+    universe.set_dead(0, 0).unwrap();
+    universe.set_cell(3, 3, Cell::Dead).unwrap();
+    assert!(universe.get_cells().iter().all(|&c| c == Cell::Dead));
+    assert!(universe.set_alive(0, 4).is_err());
+}
+
+#[test]
+fn set_cells_flat_is_all_or_nothing() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.set_cells_flat(&[0, 1, 2, 3]).unwrap();
+    assert!(universe.is_alive(0, 1) && universe.is_alive(2, 3));
+
+    universe.clear();
+    assert_eq!(
+        universe.set_cells_flat(&[0, 0, 9, 9]),
+        Err(Error::OutOfBounds { row: 9, col: 9 })
+    );
+    assert!(!universe.is_alive(0, 0));
+    assert_eq!(
+        universe.set_cells_flat(&[0, 0, 1]),
+        Err(Error::OddCoordinates(3))
+    );
+}
+
+#[test]
+fn changed_cells_replay_a_tick() {
+    let mut universe = Universe::new_with_seed(16, 16, 4).unwrap();
+    let mut follower = universe.duplicate();
+    let before = universe.get_cells().to_vec();
+
+    universe.tick();
+    follower.apply_delta(&universe.changed_cells()).unwrap();
+    assert_eq!(follower.get_cells(), universe.get_cells());
+
+    follower.apply_delta(&universe.changed_cells()).unwrap();
+    assert_eq!(follower.get_cells(), &before[..]);
+    assert!(follower.apply_delta(&[0, 16]).is_err());
+}
+
+#[test]
+fn edits_near_limits_do_not_panic() {
+    let mut tiny = Universe::new_empty(2, 2).unwrap();
+    tiny.generate_glider();
+
+    let mut universe = UniverseBuilder::new()
+        .size(5, 5)
+        .density(0.0)
+        .pattern("glider", u32::MAX, u32::MAX)
+        .build()
+        .unwrap();
+    let piece = Universe::from_text("O").unwrap();
+    universe.paste(&piece, u32::MAX, u32::MAX, BlendMode::Or);
+    universe.stamp_text(u32::MAX, 3, "A");
+
+    assert!(universe.set_alive(5, 0).is_err());
+    assert!(universe.toggle_cell(0, u32::MAX).is_err());
+    assert!(universe.get_cell(u32::MAX, 0).is_err());
+}
+
+#[test]
+fn index_and_iterators() {
+    let mut universe = Universe::new_empty(3, 2).unwrap();
+    universe[(1, 2)] = Cell::Alive;
+    universe[(0, 1)] = Cell::Alive;
+    assert_eq!(universe[(1, 2)], Cell::Alive);
+    assert_eq!(universe[(1, 1)], Cell::Dead);
+
+    let live: Vec<_> = universe.iter_live_cells().collect();
+    assert_eq!(live, vec![(0, 1), (1, 2)]);
+    assert_eq!(universe.iter_cells().count(), 6);
+    assert_eq!(universe.iter_cells().nth(4), Some((1, 1, Cell::Dead)));
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds_panics() {
+    let universe = Universe::new_empty(3, 2).unwrap();
+    let _ = universe[(0, 3)];
+}
+
+#[test]
+fn live_cells_are_flat_pairs() {
+    let mut universe = Universe::new_empty(4, 4).unwrap();
+    universe.set_cells_flat(&[3, 0, 1, 2]).unwrap();
+    assert_eq!(universe.live_cells(), vec![1, 2, 3, 0]);
+}
+
+#[test]
+fn walls_stay_dead_and_block_neighbors() {
+    // A horizontal blinker would grow into the walled cell above its middle
+    let mut universe = Universe::from_text(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    universe.set_wall(1, 2, true).unwrap();
+    universe.tick();
+    assert!(universe.is_wall(1, 2));
+    assert_eq!(universe.live_cells(), vec![2, 2, 3, 2]);
+
+    // Cells painted onto a wall die on the next tick and never count as neighbors
+    let mut block = Universe::from_text("....\n.OO.\n.O..\n....").unwrap();
+    block.set_wall(2, 2, true).unwrap();
+    block.set_cell(2, 2, Cell::Alive).unwrap();
+    block.tick();
+    assert!(!block.is_alive(2, 2));
+    assert_eq!(block.live_cells(), vec![1, 1, 1, 2, 2, 1]);
+
+    // Walls follow transforms and undo
+    block.rotate_cw();
+    assert!(block.is_wall(2, 1));
+    block.undo();
+    assert!(block.is_wall(2, 2));
+    block.clear_walls();
+    assert!(!block.is_wall(2, 2));
+}
+
+#[test]
+fn anchors_stay_alive() {
+    // A lone cell would die, but an anchor survives and keeps feeding its neighbors
+    let mut universe = Universe::new_empty(5, 5).unwrap();
+    universe.set_anchor(2, 2, true).unwrap();
+    universe.set_alive(2, 1).unwrap();
+    universe.set_alive(2, 3).unwrap();
+    universe.tick();
+    assert!(universe.is_anchor(2, 2));
+    assert_eq!(universe.live_cells(), vec![1, 2, 2, 2, 3, 2]);
+
+    // Killing an anchor by hand is undone on the next tick
+    universe.set_dead(2, 2).unwrap();
+    universe.tick();
+    assert!(universe.is_alive(2, 2));
+
+    // A wall replaces an anchor on the same cell
+    universe.set_wall(2, 2, true).unwrap();
+    assert!(!universe.is_anchor(2, 2));
+    assert!(!universe.is_alive(2, 2));
+}
+
+#[test]
+fn tags_are_kept_but_ignored_by_tick() {
+    let mut universe = Universe::from_text(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    assert!(!universe.has_tags());
+    assert_eq!(universe.tag(2, 2), 0);
+
+    universe.set_tag(2, 1, 7).unwrap();
+    universe.set_tag(0, 0, 3).unwrap();
+    assert!(universe.set_tag(5, 0, 1).is_err());
+    universe.tick();
+    assert_eq!(universe.live_cells(), vec![1, 2, 2, 2, 3, 2]);
+    assert_eq!(universe.tag(2, 1), 7);
+
+    universe.flip_horizontal();
+    assert_eq!(universe.tag(0, 4), 3);
+    assert_eq!(universe.copy_region(0, 3, 2, 2).unwrap().tag(0, 1), 3);
+
+    universe.clear_tags();
+    assert!(!universe.has_tags());
+    universe.undo();
+    assert_eq!(universe.tag(0, 4), 3);
+}
+
+#[test]
+fn ticks_alternate_between_two_buffers() {
+    let mut universe = Universe::new_with_seed(32, 32, 4).unwrap();
+    let mut expected = universe.duplicate();
+    let first = universe.cells();
+    universe.tick();
+    let second = universe.cells();
+    assert_ne!(first, second);
+    universe.tick();
+    assert_eq!(universe.cells(), first);
+    universe.tick();
+    assert_eq!(universe.cells(), second);
+
+    for _ in 0..3 {
+        expected.tick();
+    }
+    assert_eq!(universe.get_cells(), expected.get_cells());
+
+    // Multi-state rules trade state layers the same way
+    universe.set_rule("/2/3").unwrap();
+    let first = universe.states();
+    universe.tick();
+    let second = universe.states();
+    assert_ne!(first, second);
+    universe.tick();
+    assert_eq!(universe.states(), first);
+}
+
+#[test]
+fn tick_n_matches_repeated_ticks() {
+    let mut universe = Universe::new_with_seed(24, 24, 12).unwrap();
+    let mut stepped = universe.duplicate();
+    universe.tick_n(50);
+    for _ in 0..50 {
+        stepped.tick();
+    }
+    assert_eq!(universe.get_cells(), stepped.get_cells());
+    assert_eq!(universe.changed_cells(), stepped.changed_cells());
+
+    universe.tick_n(0);
+    assert_eq!(universe.get_cells(), stepped.get_cells());
+}